@@ -0,0 +1,6 @@
+//! Organization-admin API surfaces: usage/costs reporting, and
+//! users/invites management. These all require an admin API key rather
+//! than a project-scoped one.
+
+pub mod usage;
+pub mod users;
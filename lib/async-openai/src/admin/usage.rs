@@ -0,0 +1,56 @@
+//! Org-admin usage and costs reporting.
+
+use serde::Deserialize;
+
+use crate::client::Client;
+use crate::config::Config;
+use crate::error::OpenAIError;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UsageBucket {
+    pub start_time: i64,
+    pub end_time: i64,
+    pub results: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UsageResponse {
+    pub object: String,
+    pub data: Vec<UsageBucket>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CostsResponse {
+    pub object: String,
+    pub data: Vec<UsageBucket>,
+}
+
+pub struct Usage<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Usage<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    /// `query` holds the endpoint-specific filters (`start_time`, `bucket_width`,
+    /// `group_by`, ...) serialized as query-string pairs by the caller.
+    pub async fn completions(&self, query: &str) -> Result<UsageResponse, OpenAIError> {
+        self.client
+            .get(&format!("/organization/usage/completions?{query}"))
+            .await
+    }
+
+    pub async fn costs(&self, query: &str) -> Result<CostsResponse, OpenAIError> {
+        self.client
+            .get(&format!("/organization/costs?{query}"))
+            .await
+    }
+}
+
+impl<C: Config> Client<C> {
+    pub fn usage(&self) -> Usage<'_, C> {
+        Usage::new(self)
+    }
+}
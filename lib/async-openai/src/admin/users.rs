@@ -0,0 +1,66 @@
+//! Organization users and invites management.
+
+use serde::{Deserialize, Serialize};
+
+use crate::client::Client;
+use crate::config::Config;
+use crate::error::OpenAIError;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OrganizationUser {
+    pub id: String,
+    pub name: String,
+    pub email: String,
+    pub role: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ListUsersResponse {
+    pub data: Vec<OrganizationUser>,
+    pub has_more: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateInviteRequest {
+    pub email: String,
+    pub role: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Invite {
+    pub id: String,
+    pub email: String,
+    pub role: String,
+    pub status: String,
+}
+
+pub struct Users<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Users<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    pub async fn list(&self) -> Result<ListUsersResponse, OpenAIError> {
+        self.client.get("/organization/users").await
+    }
+
+    pub async fn remove(&self, user_id: &str) -> Result<(), OpenAIError> {
+        self.client
+            .post::<(), serde_json::Value>(&format!("/organization/users/{user_id}/delete"), &())
+            .await
+            .map(|_| ())
+    }
+
+    pub async fn invite(&self, request: &CreateInviteRequest) -> Result<Invite, OpenAIError> {
+        self.client.post("/organization/invites", request).await
+    }
+}
+
+impl<C: Config> Client<C> {
+    pub fn org_users(&self) -> Users<'_, C> {
+        Users::new(self)
+    }
+}
@@ -0,0 +1,87 @@
+//! Assistants API: persistent assistants, threads, and runs, plus the
+//! typed streaming events a run emits when created with `stream: true`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::client::Client;
+use crate::config::Config;
+use crate::error::OpenAIError;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateAssistantRequest {
+    pub model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<serde_json::Value>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Assistant {
+    pub id: String,
+    pub model: String,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Thread {
+    pub id: String,
+    pub object: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Run {
+    pub id: String,
+    pub thread_id: String,
+    pub status: String,
+}
+
+/// Server-sent events emitted over the course of a streamed run, named
+/// after the `event:` line the API sends (not the `data:` payload's own
+/// `object` field, which is coarser).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "event", content = "data")]
+pub enum AssistantStreamEvent {
+    #[serde(rename = "thread.run.created")]
+    ThreadRunCreated(Run),
+    #[serde(rename = "thread.run.queued")]
+    ThreadRunQueued(Run),
+    #[serde(rename = "thread.run.in_progress")]
+    ThreadRunInProgress(Run),
+    #[serde(rename = "thread.run.completed")]
+    ThreadRunCompleted(Run),
+    #[serde(rename = "thread.run.failed")]
+    ThreadRunFailed(Run),
+    #[serde(rename = "thread.message.delta")]
+    ThreadMessageDelta(serde_json::Value),
+    #[serde(rename = "thread.run.step.delta")]
+    ThreadRunStepDelta(serde_json::Value),
+    #[serde(other)]
+    Unknown,
+}
+
+pub struct Assistants<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Assistants<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    pub async fn create(&self, request: &CreateAssistantRequest) -> Result<Assistant, OpenAIError> {
+        self.client.post("/assistants", request).await
+    }
+
+    pub async fn create_thread(&self) -> Result<Thread, OpenAIError> {
+        self.client.post("/threads", &serde_json::json!({})).await
+    }
+}
+
+impl<C: Config> Client<C> {
+    pub fn assistants(&self) -> Assistants<'_, C> {
+        Assistants::new(self)
+    }
+}
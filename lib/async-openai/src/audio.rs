@@ -0,0 +1,140 @@
+//! Audio endpoints: text-to-speech, transcription, and translation.
+//! Text-to-speech also has a streaming variant for low-latency playback;
+//! transcription and translation return only the complete result.
+
+use bytes::Bytes;
+use futures::Stream;
+use reqwest::header::AUTHORIZATION;
+use serde::{Deserialize, Serialize};
+
+use crate::client::Client;
+use crate::config::Config;
+use crate::error::OpenAIError;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateSpeechRequest {
+    pub model: String,
+    pub input: String,
+    pub voice: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateTranscriptionResponse {
+    pub text: String,
+}
+
+pub struct Audio<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Audio<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    /// Non-streaming text-to-speech: returns the full audio payload.
+    pub async fn speech(&self, request: &CreateSpeechRequest) -> Result<Bytes, OpenAIError> {
+        let response = self
+            .client
+            .http()
+            .post(self.client.config().url("/audio/speech"))
+            .headers(self.client.config().headers())
+            .header(
+                AUTHORIZATION,
+                format!("Bearer {}", self.client.config().api_key()),
+            )
+            .json(request)
+            .send()
+            .await?;
+        Ok(response.bytes().await?)
+    }
+
+    /// Streaming text-to-speech: yields audio chunks as they're generated
+    /// rather than buffering the whole clip.
+    pub fn speech_stream(
+        &self,
+        request: &CreateSpeechRequest,
+    ) -> impl Stream<Item = Result<Bytes, OpenAIError>> + '_ {
+        let mut streamed = request.clone();
+        streamed.stream = Some(true);
+        let request_builder = self
+            .client
+            .http()
+            .post(self.client.config().url("/audio/speech"))
+            .headers(self.client.config().headers())
+            .header(
+                AUTHORIZATION,
+                format!("Bearer {}", self.client.config().api_key()),
+            )
+            .json(&streamed);
+
+        async_stream::try_stream! {
+            let response = request_builder.send().await?;
+            let mut bytes_stream = response.bytes_stream();
+            while let Some(chunk) = futures::StreamExt::next(&mut bytes_stream).await {
+                yield chunk?;
+            }
+        }
+    }
+
+    pub async fn transcribe(
+        &self,
+        file_bytes: Vec<u8>,
+        file_name: &str,
+        model: &str,
+    ) -> Result<CreateTranscriptionResponse, OpenAIError> {
+        let form = reqwest::multipart::Form::new()
+            .text("model", model.to_string())
+            .part(
+                "file",
+                reqwest::multipart::Part::bytes(file_bytes).file_name(file_name.to_string()),
+            );
+        self.multipart_post("/audio/transcriptions", form).await
+    }
+
+    pub async fn translate(
+        &self,
+        file_bytes: Vec<u8>,
+        file_name: &str,
+        model: &str,
+    ) -> Result<CreateTranscriptionResponse, OpenAIError> {
+        let form = reqwest::multipart::Form::new()
+            .text("model", model.to_string())
+            .part(
+                "file",
+                reqwest::multipart::Part::bytes(file_bytes).file_name(file_name.to_string()),
+            );
+        self.multipart_post("/audio/translations", form).await
+    }
+
+    async fn multipart_post(
+        &self,
+        path: &str,
+        form: reqwest::multipart::Form,
+    ) -> Result<CreateTranscriptionResponse, OpenAIError> {
+        let response = self
+            .client
+            .http()
+            .post(self.client.config().url(path))
+            .headers(self.client.config().headers())
+            .header(
+                AUTHORIZATION,
+                format!("Bearer {}", self.client.config().api_key()),
+            )
+            .multipart(form)
+            .send()
+            .await?;
+        let bytes = response.bytes().await?;
+        serde_json::from_slice(&bytes).map_err(OpenAIError::JSONDeserialize)
+    }
+}
+
+impl<C: Config> Client<C> {
+    pub fn audio(&self) -> Audio<'_, C> {
+        Audio::new(self)
+    }
+}
@@ -0,0 +1,70 @@
+//! Batch API: submit a JSONL file of requests for asynchronous, discounted
+//! processing instead of calling each endpoint inline.
+
+use serde::{Deserialize, Serialize};
+
+use crate::client::Client;
+use crate::config::Config;
+use crate::error::OpenAIError;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateBatchRequest {
+    pub input_file_id: String,
+    pub endpoint: String,
+    pub completion_window: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Batch {
+    pub id: String,
+    pub object: String,
+    pub endpoint: String,
+    pub status: String,
+    pub input_file_id: String,
+    pub output_file_id: Option<String>,
+    pub error_file_id: Option<String>,
+    pub created_at: i64,
+    pub completed_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ListBatchesResponse {
+    pub data: Vec<Batch>,
+    pub has_more: bool,
+}
+
+pub struct Batches<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Batches<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    pub async fn create(&self, request: CreateBatchRequest) -> Result<Batch, OpenAIError> {
+        self.client.post("/batches", &request).await
+    }
+
+    pub async fn retrieve(&self, batch_id: &str) -> Result<Batch, OpenAIError> {
+        self.client.get(&format!("/batches/{batch_id}")).await
+    }
+
+    pub async fn cancel(&self, batch_id: &str) -> Result<Batch, OpenAIError> {
+        self.client
+            .post(&format!("/batches/{batch_id}/cancel"), &())
+            .await
+    }
+
+    pub async fn list(&self) -> Result<ListBatchesResponse, OpenAIError> {
+        self.client.get("/batches").await
+    }
+}
+
+impl<C: Config> Client<C> {
+    pub fn batches(&self) -> Batches<'_, C> {
+        Batches::new(self)
+    }
+}
@@ -0,0 +1,126 @@
+//! Chat Completions: the core of this crate, and the one endpoint every
+//! other module (tool results, token counting, byot, ...) ends up
+//! referencing the request/response types of.
+
+use futures::Stream;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::client::Client;
+use crate::config::Config;
+use crate::error::OpenAIError;
+use crate::sse::stream_with_resume;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "role", rename_all = "snake_case")]
+pub enum ChatCompletionRequestMessage {
+    System { content: String },
+    User { content: String },
+    Assistant {
+        content: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        tool_calls: Option<Vec<serde_json::Value>>,
+    },
+    Tool {
+        content: String,
+        tool_call_id: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateChatCompletionRequest {
+    pub model: String,
+    pub messages: Vec<ChatCompletionRequestMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<serde_json::Value>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChatCompletionResponseMessage {
+    pub role: String,
+    pub content: Option<String>,
+    pub tool_calls: Option<Vec<serde_json::Value>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChatChoice {
+    pub index: u32,
+    pub message: ChatCompletionResponseMessage,
+    pub finish_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Usage {
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+    pub total_tokens: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateChatCompletionResponse {
+    pub id: String,
+    pub model: String,
+    pub choices: Vec<ChatChoice>,
+    pub usage: Option<Usage>,
+}
+
+pub struct Chat<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Chat<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    pub async fn create(
+        &self,
+        request: &CreateChatCompletionRequest,
+    ) -> Result<CreateChatCompletionResponse, OpenAIError> {
+        let response: CreateChatCompletionResponse =
+            self.client.post("/chat/completions", request).await?;
+        if let Some(usage) = &response.usage {
+            self.client
+                .metrics()
+                .record_tokens(usage.prompt_tokens as u64, usage.completion_tokens as u64);
+        }
+        Ok(response)
+    }
+
+    /// Streaming chat completions, BYOT-style: `I`/`O` let the caller pass
+    /// a request/response shape other than this crate's own (a model with
+    /// extra sampling params, a backend with extra fields on the stream
+    /// chunk, ...) without forking the whole endpoint. `request` must
+    /// already have its `stream` field set to `true`.
+    pub fn create_stream_byot<I, O>(
+        &self,
+        request: I,
+    ) -> impl Stream<Item = Result<O, OpenAIError>> + '_
+    where
+        I: Serialize + 'static,
+        O: DeserializeOwned + 'static,
+    {
+        let body = serde_json::to_value(request).unwrap_or(serde_json::Value::Null);
+        stream_with_resume(self.client, "/chat/completions".to_string(), body, 2)
+    }
+
+    /// Non-streaming BYOT escape hatch, for completeness alongside the
+    /// streaming one above.
+    pub async fn create_byot<I, O>(&self, request: &I) -> Result<O, OpenAIError>
+    where
+        I: Serialize + ?Sized,
+        O: DeserializeOwned,
+    {
+        self.client.post("/chat/completions", request).await
+    }
+}
+
+impl<C: Config> Client<C> {
+    pub fn chat(&self) -> Chat<'_, C> {
+        Chat::new(self)
+    }
+}
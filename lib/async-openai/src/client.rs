@@ -0,0 +1,219 @@
+//! Thin async HTTP client shared by every API surface in this crate.
+
+use std::sync::{Arc, Mutex};
+
+use reqwest::header::{HeaderValue, AUTHORIZATION};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::config::{Config, OpenAIConfig};
+use crate::error::{ApiError, OpenAIError};
+use crate::interceptor::Interceptor;
+use crate::metrics::ClientMetrics;
+use crate::rate_limit::RateLimitState;
+use crate::request_options::RequestOptions;
+use crate::retry::{retry_after_or_backoff, RetryConfig};
+
+/// Entry point for every API surface (`client.chat()`, `client.realtime()`,
+/// ...). Generic over [`Config`] so callers can point the same client code
+/// at OpenAI, Azure, or a self-hosted Dynamo frontend.
+#[derive(Clone)]
+pub struct Client<C: Config = OpenAIConfig> {
+    http: reqwest::Client,
+    config: Arc<C>,
+    retry: RetryConfig,
+    rate_limit: Arc<Mutex<RateLimitState>>,
+    interceptors: Arc<Vec<Arc<dyn Interceptor>>>,
+    metrics: ClientMetrics,
+}
+
+impl<C: Config> Client<C> {
+    pub fn build(http: reqwest::Client, config: C) -> Self {
+        Self {
+            http,
+            config: Arc::new(config),
+            retry: RetryConfig::default(),
+            rate_limit: Arc::new(Mutex::new(RateLimitState::default())),
+            interceptors: Arc::new(Vec::new()),
+            metrics: ClientMetrics::default(),
+        }
+    }
+
+    /// Latency/retry/token counters accumulated across every call this
+    /// client has made. Cloning the handle is cheap and keeps counting
+    /// against the same underlying totals.
+    pub fn metrics(&self) -> ClientMetrics {
+        self.metrics.clone()
+    }
+
+    /// Register an interceptor to run on every request/response this
+    /// client makes, in addition to any already registered.
+    pub fn with_interceptor(mut self, interceptor: Arc<dyn Interceptor>) -> Self {
+        let mut interceptors = (*self.interceptors).clone();
+        interceptors.push(interceptor);
+        self.interceptors = Arc::new(interceptors);
+        self
+    }
+
+    /// Rate-limit state observed on the most recently completed request,
+    /// as reported by the server's `x-ratelimit-*` headers.
+    pub fn rate_limit_state(&self) -> RateLimitState {
+        *self.rate_limit.lock().expect("rate limit mutex poisoned")
+    }
+
+    pub fn with_retry_config(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn config(&self) -> &C {
+        &self.config
+    }
+
+    pub fn http(&self) -> &reqwest::Client {
+        &self.http
+    }
+
+    pub async fn post<I, O>(&self, path: &str, body: &I) -> Result<O, OpenAIError>
+    where
+        I: Serialize + ?Sized,
+        O: DeserializeOwned,
+    {
+        self.post_with_options(path, body, &RequestOptions::default())
+            .await
+    }
+
+    pub async fn post_with_options<I, O>(
+        &self,
+        path: &str,
+        body: &I,
+        options: &RequestOptions,
+    ) -> Result<O, OpenAIError>
+    where
+        I: Serialize + ?Sized,
+        O: DeserializeOwned,
+    {
+        self.send_with_retry(
+            || {
+                self.http
+                    .post(self.config.url(path))
+                    .headers(self.config.headers())
+                    .json(body)
+            },
+            options,
+        )
+        .await
+    }
+
+    pub async fn get<O>(&self, path: &str) -> Result<O, OpenAIError>
+    where
+        O: DeserializeOwned,
+    {
+        self.get_with_options(path, &RequestOptions::default())
+            .await
+    }
+
+    pub async fn get_with_options<O>(
+        &self,
+        path: &str,
+        options: &RequestOptions,
+    ) -> Result<O, OpenAIError>
+    where
+        O: DeserializeOwned,
+    {
+        self.send_with_retry(
+            || {
+                self.http
+                    .get(self.config.url(path))
+                    .headers(self.config.headers())
+            },
+            options,
+        )
+        .await
+    }
+
+    /// Execute a request builder (rebuilt fresh on each attempt, since
+    /// `reqwest::RequestBuilder` isn't cloneable once headers are set),
+    /// retrying transient failures per [`RetryConfig`].
+    async fn send_with_retry<O, F>(
+        &self,
+        build: F,
+        options: &RequestOptions,
+    ) -> Result<O, OpenAIError>
+    where
+        O: DeserializeOwned,
+        F: Fn() -> reqwest::RequestBuilder,
+    {
+        let mut attempt = 0;
+        let started_at = std::time::Instant::now();
+        loop {
+            let mut builder = build().header(
+                AUTHORIZATION,
+                HeaderValue::from_str(&format!("Bearer {}", self.config.api_key()))
+                    .map_err(|e| OpenAIError::InvalidArgument(e.to_string()))?,
+            );
+            if let Some(timeout) = options.timeout {
+                builder = builder.timeout(timeout);
+            }
+            for (key, value) in &options.extra_headers {
+                builder = builder.header(key, value);
+            }
+            if !options.extra_query.is_empty() {
+                builder = builder.query(&options.extra_query);
+            }
+            if let Some(key) = &options.idempotency_key {
+                builder = builder.header("Idempotency-Key", key);
+            }
+            let mut request = builder.build().map_err(OpenAIError::Reqwest)?;
+
+            for interceptor in self.interceptors.iter() {
+                request = interceptor.before_request(request).await;
+            }
+
+            let response = self.http.execute(request).await?;
+
+            for interceptor in self.interceptors.iter().rev() {
+                interceptor.after_response(&response).await;
+            }
+
+            let status = response.status();
+            *self.rate_limit.lock().expect("rate limit mutex poisoned") =
+                RateLimitState::from_headers(response.headers());
+
+            if self.retry.should_retry(attempt, status) {
+                let delay = retry_after_or_backoff(response.headers(), &self.retry, attempt);
+                crate::time::sleep(delay).await;
+                attempt += 1;
+                continue;
+            }
+
+            self.metrics
+                .record_request(started_at.elapsed(), attempt);
+            return Self::handle_response(response).await;
+        }
+    }
+
+    async fn handle_response<O: DeserializeOwned>(
+        response: reqwest::Response,
+    ) -> Result<O, OpenAIError> {
+        let status = response.status();
+        let bytes = response.bytes().await?;
+        if status.is_success() {
+            serde_json::from_slice(&bytes).map_err(OpenAIError::JSONDeserialize)
+        } else {
+            #[derive(serde::Deserialize)]
+            struct Envelope {
+                error: ApiError,
+            }
+            let envelope: Envelope =
+                serde_json::from_slice(&bytes).map_err(OpenAIError::JSONDeserialize)?;
+            Err(OpenAIError::ApiError(envelope.error))
+        }
+    }
+}
+
+impl Default for Client<OpenAIConfig> {
+    fn default() -> Self {
+        Self::build(reqwest::Client::new(), OpenAIConfig::default())
+    }
+}
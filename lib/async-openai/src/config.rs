@@ -0,0 +1,127 @@
+//! Endpoint and credential configuration for [`crate::Client`].
+
+/// Anything that can supply a base URL, an API key, and extra headers for
+/// an outgoing request. Kept as a trait (rather than a single struct) so
+/// deployments can swap in [`AzureConfig`] or a custom implementation
+/// without touching `Client` itself.
+pub trait Config: Clone + Send + Sync {
+    fn api_base(&self) -> &str;
+    fn api_key(&self) -> &str;
+    fn headers(&self) -> reqwest::header::HeaderMap;
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.api_base(), path)
+    }
+}
+
+/// Config for talking to OpenAI itself, or any OpenAI-compatible endpoint
+/// (vLLM, TRT-LLM, Dynamo's own frontend).
+#[derive(Debug, Clone)]
+pub struct OpenAIConfig {
+    api_base: String,
+    api_key: String,
+    org_id: Option<String>,
+}
+
+impl Default for OpenAIConfig {
+    fn default() -> Self {
+        Self {
+            api_base: "https://api.openai.com/v1".to_string(),
+            api_key: std::env::var("OPENAI_API_KEY").unwrap_or_default(),
+            org_id: std::env::var("OPENAI_ORG_ID").ok(),
+        }
+    }
+}
+
+impl OpenAIConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_api_base(mut self, api_base: impl Into<String>) -> Self {
+        self.api_base = api_base.into();
+        self
+    }
+
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = api_key.into();
+        self
+    }
+
+    pub fn with_org_id(mut self, org_id: impl Into<String>) -> Self {
+        self.org_id = Some(org_id.into());
+        self
+    }
+}
+
+impl Config for OpenAIConfig {
+    fn api_base(&self) -> &str {
+        &self.api_base
+    }
+
+    fn api_key(&self) -> &str {
+        &self.api_key
+    }
+
+    fn headers(&self) -> reqwest::header::HeaderMap {
+        let mut headers = reqwest::header::HeaderMap::new();
+        if let Some(org_id) = &self.org_id {
+            if let Ok(value) = reqwest::header::HeaderValue::from_str(org_id) {
+                headers.insert("OpenAI-Organization", value);
+            }
+        }
+        headers
+    }
+}
+
+/// Config for Azure OpenAI deployments. Azure uses `api-key` instead of a
+/// bearer token and addresses models by deployment id rather than name, so
+/// it needs its own `url()` shape (`/openai/deployments/{id}/...`) on top
+/// of the resource's base URL.
+#[derive(Debug, Clone)]
+pub struct AzureConfig {
+    api_base: String,
+    api_key: String,
+    api_version: String,
+    deployment_id: String,
+}
+
+impl AzureConfig {
+    pub fn new(
+        api_base: impl Into<String>,
+        api_key: impl Into<String>,
+        api_version: impl Into<String>,
+        deployment_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            api_base: api_base.into(),
+            api_key: api_key.into(),
+            api_version: api_version.into(),
+            deployment_id: deployment_id.into(),
+        }
+    }
+}
+
+impl Config for AzureConfig {
+    fn api_base(&self) -> &str {
+        &self.api_base
+    }
+
+    fn api_key(&self) -> &str {
+        &self.api_key
+    }
+
+    fn headers(&self) -> reqwest::header::HeaderMap {
+        let mut headers = reqwest::header::HeaderMap::new();
+        if let Ok(value) = reqwest::header::HeaderValue::from_str(&self.api_key) {
+            headers.insert("api-key", value);
+        }
+        headers
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!(
+            "{}/openai/deployments/{}{}?api-version={}",
+            self.api_base, self.deployment_id, path, self.api_version
+        )
+    }
+}
@@ -0,0 +1,109 @@
+//! Embeddings API. Supports both the default `float` encoding and the more
+//! compact `base64` one, decoding the latter back into `Vec<f32>` so
+//! callers never have to deal with the wire encoding themselves.
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use crate::client::Client;
+use crate::config::Config;
+use crate::error::OpenAIError;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateEmbeddingRequest {
+    pub model: String,
+    pub input: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding_format: Option<String>,
+}
+
+/// An embedding's raw wire representation: either a plain float array, or
+/// a base64-encoded little-endian `f32` buffer (smaller over the wire).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum EmbeddingValue {
+    Float(Vec<f32>),
+    Base64(String),
+}
+
+impl EmbeddingValue {
+    /// Decode into a flat `Vec<f32>` regardless of wire encoding.
+    pub fn decode(&self) -> Result<Vec<f32>, OpenAIError> {
+        match self {
+            EmbeddingValue::Float(values) => Ok(values.clone()),
+            EmbeddingValue::Base64(encoded) => decode_base64_embedding(encoded),
+        }
+    }
+}
+
+fn decode_base64_embedding(encoded: &str) -> Result<Vec<f32>, OpenAIError> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|e| OpenAIError::InvalidArgument(e.to_string()))?;
+    if bytes.len() % 4 != 0 {
+        return Err(OpenAIError::InvalidArgument(
+            "base64 embedding payload is not a whole number of f32s".to_string(),
+        ));
+    }
+    Ok(bytes
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect())
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Embedding {
+    pub index: u32,
+    pub embedding: EmbeddingValue,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateEmbeddingResponse {
+    pub model: String,
+    pub data: Vec<Embedding>,
+}
+
+pub struct Embeddings<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Embeddings<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    pub async fn create(
+        &self,
+        request: &CreateEmbeddingRequest,
+    ) -> Result<CreateEmbeddingResponse, OpenAIError> {
+        self.client.post("/embeddings", request).await
+    }
+}
+
+impl<C: Config> Client<C> {
+    pub fn embeddings(&self) -> Embeddings<'_, C> {
+        Embeddings::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_base64_roundtrip() {
+        let original = vec![1.0_f32, -2.5, 3.25];
+        let bytes: Vec<u8> = original.iter().flat_map(|f| f.to_le_bytes()).collect();
+        let encoded = base64::engine::general_purpose::STANDARD.encode(&bytes);
+        let value = EmbeddingValue::Base64(encoded);
+        assert_eq!(value.decode().unwrap(), original);
+    }
+
+    #[test]
+    fn rejects_truncated_payload() {
+        let value = EmbeddingValue::Base64(
+            base64::engine::general_purpose::STANDARD.encode([1, 2, 3]),
+        );
+        assert!(value.decode().is_err());
+    }
+}
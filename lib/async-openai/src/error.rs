@@ -0,0 +1,36 @@
+//! Error type returned by every [`crate::Client`] call.
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum OpenAIError {
+    #[error("http error: {0}")]
+    Reqwest(#[from] reqwest::Error),
+
+    #[error("failed to deserialize api response: {0}")]
+    JSONDeserialize(serde_json::Error),
+
+    #[error("api returned an error: {0}")]
+    ApiError(ApiError),
+
+    #[error("invalid argument: {0}")]
+    InvalidArgument(String),
+
+    #[error("stream ended unexpectedly")]
+    StreamError(String),
+}
+
+/// Mirrors the `{"error": {...}}` envelope the API wraps non-2xx responses in.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ApiError {
+    pub message: String,
+    #[serde(rename = "type")]
+    pub error_type: Option<String>,
+    pub code: Option<String>,
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
@@ -0,0 +1,85 @@
+//! A [`Config`] that rotates across multiple API keys and can fail over to
+//! a backup endpoint, for deployments that hit per-key rate limits or need
+//! to survive a single endpoint going down.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::config::Config;
+
+#[derive(Debug, Clone)]
+struct Endpoint {
+    api_base: String,
+    api_key: String,
+}
+
+/// Cycles through a list of `(api_base, api_key)` pairs on every call to
+/// [`Self::advance`]. [`Config::api_base`]/[`Config::api_key`] always
+/// return whichever endpoint is currently selected; callers drive rotation
+/// explicitly (typically from a retry/error-handling layer) rather than
+/// having it happen implicitly per-request, so a mid-stream failover
+/// doesn't change the endpoint underneath a request that's already in
+/// flight.
+#[derive(Debug, Clone)]
+pub struct FailoverConfig {
+    endpoints: Arc<Vec<Endpoint>>,
+    current: Arc<AtomicUsize>,
+}
+
+impl FailoverConfig {
+    pub fn new(endpoints: Vec<(impl Into<String>, impl Into<String>)>) -> Self {
+        assert!(!endpoints.is_empty(), "FailoverConfig needs at least one endpoint");
+        Self {
+            endpoints: Arc::new(
+                endpoints
+                    .into_iter()
+                    .map(|(api_base, api_key)| Endpoint {
+                        api_base: api_base.into(),
+                        api_key: api_key.into(),
+                    })
+                    .collect(),
+            ),
+            current: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Rotate to the next endpoint in the list, wrapping around. Call this
+    /// when the current endpoint returns a rate-limit or connection error.
+    pub fn advance(&self) {
+        self.current.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn current_endpoint(&self) -> &Endpoint {
+        let index = self.current.load(Ordering::Relaxed) % self.endpoints.len();
+        &self.endpoints[index]
+    }
+}
+
+impl Config for FailoverConfig {
+    fn api_base(&self) -> &str {
+        &self.current_endpoint().api_base
+    }
+
+    fn api_key(&self) -> &str {
+        &self.current_endpoint().api_key
+    }
+
+    fn headers(&self) -> reqwest::header::HeaderMap {
+        reqwest::header::HeaderMap::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advance_wraps_around() {
+        let config = FailoverConfig::new(vec![("a", "key-a"), ("b", "key-b")]);
+        assert_eq!(config.api_base(), "a");
+        config.advance();
+        assert_eq!(config.api_base(), "b");
+        config.advance();
+        assert_eq!(config.api_base(), "a");
+    }
+}
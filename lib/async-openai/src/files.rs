@@ -0,0 +1,81 @@
+//! Files API: upload files for use by other endpoints (fine-tuning, batch,
+//! vector stores, ...) and retrieve their metadata/content back.
+
+use std::path::Path;
+
+use futures::StreamExt;
+use serde::Deserialize;
+use tokio::io::AsyncWriteExt;
+
+use crate::client::Client;
+use crate::config::Config;
+use crate::error::OpenAIError;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenAIFile {
+    pub id: String,
+    pub bytes: u64,
+    pub filename: String,
+    pub purpose: String,
+}
+
+pub struct Files<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Files<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    pub async fn retrieve(&self, file_id: &str) -> Result<OpenAIFile, OpenAIError> {
+        self.client.get(&format!("/files/{file_id}")).await
+    }
+
+    /// Streams `/files/{file_id}/content` straight to `dest` instead of
+    /// buffering the whole body in memory first, for large fine-tuning
+    /// result files and the like.
+    pub async fn download_to_file(
+        &self,
+        file_id: &str,
+        dest: impl AsRef<Path>,
+    ) -> Result<(), OpenAIError> {
+        let response = self
+            .client
+            .http()
+            .get(
+                self.client
+                    .config()
+                    .url(&format!("/files/{file_id}/content")),
+            )
+            .headers(self.client.config().headers())
+            .header(
+                reqwest::header::AUTHORIZATION,
+                format!("Bearer {}", self.client.config().api_key()),
+            )
+            .send()
+            .await?;
+
+        let mut file = tokio::fs::File::create(dest)
+            .await
+            .map_err(|e| OpenAIError::StreamError(e.to_string()))?;
+
+        let mut stream = response.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            file.write_all(&chunk)
+                .await
+                .map_err(|e| OpenAIError::StreamError(e.to_string()))?;
+        }
+        file.flush()
+            .await
+            .map_err(|e| OpenAIError::StreamError(e.to_string()))?;
+        Ok(())
+    }
+}
+
+impl<C: Config> Client<C> {
+    pub fn files(&self) -> Files<'_, C> {
+        Files::new(self)
+    }
+}
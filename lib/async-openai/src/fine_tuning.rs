@@ -0,0 +1,84 @@
+//! Fine-tuning API: launch and monitor fine-tuning jobs over uploaded
+//! training files.
+
+use serde::{Deserialize, Serialize};
+
+use crate::client::Client;
+use crate::config::Config;
+use crate::error::OpenAIError;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateFineTuningJobRequest {
+    pub training_file: String,
+    pub model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validation_file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hyperparameters: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FineTuningJob {
+    pub id: String,
+    pub object: String,
+    pub model: String,
+    pub status: String,
+    pub fine_tuned_model: Option<String>,
+    pub trained_tokens: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FineTuningJobEvent {
+    pub id: String,
+    pub level: String,
+    pub message: String,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ListFineTuningEventsResponse {
+    pub data: Vec<FineTuningJobEvent>,
+    pub has_more: bool,
+}
+
+pub struct FineTuning<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> FineTuning<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    pub async fn create(
+        &self,
+        request: CreateFineTuningJobRequest,
+    ) -> Result<FineTuningJob, OpenAIError> {
+        self.client.post("/fine_tuning/jobs", &request).await
+    }
+
+    pub async fn retrieve(&self, job_id: &str) -> Result<FineTuningJob, OpenAIError> {
+        self.client.get(&format!("/fine_tuning/jobs/{job_id}")).await
+    }
+
+    pub async fn cancel(&self, job_id: &str) -> Result<FineTuningJob, OpenAIError> {
+        self.client
+            .post(&format!("/fine_tuning/jobs/{job_id}/cancel"), &())
+            .await
+    }
+
+    pub async fn list_events(
+        &self,
+        job_id: &str,
+    ) -> Result<ListFineTuningEventsResponse, OpenAIError> {
+        self.client
+            .get(&format!("/fine_tuning/jobs/{job_id}/events"))
+            .await
+    }
+}
+
+impl<C: Config> Client<C> {
+    pub fn fine_tuning(&self) -> FineTuning<'_, C> {
+        FineTuning::new(self)
+    }
+}
@@ -0,0 +1,96 @@
+//! Images API: generate, edit, and create variations of images.
+
+use serde::{Deserialize, Serialize};
+
+use crate::client::Client;
+use crate::config::Config;
+use crate::error::OpenAIError;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateImageRequest {
+    pub prompt: String,
+    pub model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Image {
+    pub url: Option<String>,
+    pub b64_json: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImagesResponse {
+    pub created: i64,
+    pub data: Vec<Image>,
+}
+
+pub struct Images<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Images<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    pub async fn create(&self, request: &CreateImageRequest) -> Result<ImagesResponse, OpenAIError> {
+        self.client.post("/images/generations", request).await
+    }
+
+    pub async fn edit(
+        &self,
+        image_bytes: Vec<u8>,
+        prompt: &str,
+        model: &str,
+    ) -> Result<ImagesResponse, OpenAIError> {
+        let form = reqwest::multipart::Form::new()
+            .text("prompt", prompt.to_string())
+            .text("model", model.to_string())
+            .part("image", reqwest::multipart::Part::bytes(image_bytes).file_name("image.png"));
+        self.multipart_post("/images/edits", form).await
+    }
+
+    pub async fn create_variation(
+        &self,
+        image_bytes: Vec<u8>,
+        model: &str,
+    ) -> Result<ImagesResponse, OpenAIError> {
+        let form = reqwest::multipart::Form::new()
+            .text("model", model.to_string())
+            .part("image", reqwest::multipart::Part::bytes(image_bytes).file_name("image.png"));
+        self.multipart_post("/images/variations", form).await
+    }
+
+    async fn multipart_post(
+        &self,
+        path: &str,
+        form: reqwest::multipart::Form,
+    ) -> Result<ImagesResponse, OpenAIError> {
+        let response = self
+            .client
+            .http()
+            .post(self.client.config().url(path))
+            .headers(self.client.config().headers())
+            .header(
+                reqwest::header::AUTHORIZATION,
+                format!("Bearer {}", self.client.config().api_key()),
+            )
+            .multipart(form)
+            .send()
+            .await?;
+        let bytes = response.bytes().await?;
+        serde_json::from_slice(&bytes).map_err(OpenAIError::JSONDeserialize)
+    }
+}
+
+impl<C: Config> Client<C> {
+    pub fn images(&self) -> Images<'_, C> {
+        Images::new(self)
+    }
+}
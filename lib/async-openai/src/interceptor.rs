@@ -0,0 +1,35 @@
+//! Request/response interceptor hooks, for cross-cutting concerns (logging,
+//! metrics, header injection) that shouldn't need to thread through every
+//! API surface module individually.
+
+use async_trait::async_trait;
+
+/// Observes (and may mutate) outgoing requests and incoming responses.
+/// Interceptors run in registration order for requests and reverse order
+/// for responses, mirroring typical middleware stacks.
+#[async_trait]
+pub trait Interceptor: Send + Sync {
+    async fn before_request(&self, request: reqwest::Request) -> reqwest::Request {
+        request
+    }
+
+    async fn after_response(&self, response: &reqwest::Response) {
+        let _ = response;
+    }
+}
+
+/// Interceptor that logs method, path, and status via `tracing`, at the
+/// level most deployments already use for access logs.
+pub struct LoggingInterceptor;
+
+#[async_trait]
+impl Interceptor for LoggingInterceptor {
+    async fn before_request(&self, request: reqwest::Request) -> reqwest::Request {
+        tracing::debug!(method = %request.method(), url = %request.url(), "openai request");
+        request
+    }
+
+    async fn after_response(&self, response: &reqwest::Response) {
+        tracing::debug!(status = %response.status(), "openai response");
+    }
+}
@@ -0,0 +1,45 @@
+//! Async client for the OpenAI-compatible HTTP API, used by Dynamo
+//! components both to call out to real OpenAI-compatible backends and to
+//! model the request/response types the frontend itself exposes.
+//!
+//! Forked from the `async-openai` crate layout: a [`Client`] generic over
+//! [`config::Config`], with one module per API surface.
+
+pub mod admin;
+pub mod assistants;
+pub mod audio;
+pub mod batches;
+pub mod chat;
+pub mod client;
+pub mod config;
+pub mod error;
+pub mod failover_config;
+pub mod fine_tuning;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod files;
+pub mod embeddings;
+pub mod images;
+pub mod interceptor;
+#[cfg(feature = "test-util")]
+pub mod mock;
+pub mod metrics;
+pub mod otel;
+pub mod moderations;
+pub mod rate_limit;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod realtime;
+pub mod request_options;
+pub mod responses;
+pub mod retry;
+pub mod sse;
+pub mod structured_outputs;
+pub mod time;
+pub mod tokens;
+pub mod tool_loop;
+pub mod transport;
+pub mod uploads;
+pub mod vector_stores;
+
+pub use client::Client;
+pub use config::{AzureConfig, Config, OpenAIConfig};
+pub use error::OpenAIError;
@@ -0,0 +1,90 @@
+//! Client-side metrics: request latency, retry counts, and token usage,
+//! aggregated in-process so a caller can export them however it likes
+//! (logs, Prometheus, a custom sink) without this crate depending on any
+//! particular metrics backend.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug, Default)]
+struct Inner {
+    requests_total: AtomicU64,
+    retries_total: AtomicU64,
+    latency_ms_total: AtomicU64,
+    prompt_tokens_total: AtomicU64,
+    completion_tokens_total: AtomicU64,
+}
+
+/// Cheaply cloneable handle to a shared set of counters. One instance is
+/// meant to live for the lifetime of a [`crate::Client`].
+#[derive(Debug, Default, Clone)]
+pub struct ClientMetrics {
+    inner: Arc<Inner>,
+}
+
+impl ClientMetrics {
+    pub fn record_request(&self, latency: Duration, retries: u32) {
+        self.inner.requests_total.fetch_add(1, Ordering::Relaxed);
+        self.inner
+            .retries_total
+            .fetch_add(retries as u64, Ordering::Relaxed);
+        self.inner
+            .latency_ms_total
+            .fetch_add(latency.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_tokens(&self, prompt_tokens: u64, completion_tokens: u64) {
+        self.inner
+            .prompt_tokens_total
+            .fetch_add(prompt_tokens, Ordering::Relaxed);
+        self.inner
+            .completion_tokens_total
+            .fetch_add(completion_tokens, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> ClientMetricsSnapshot {
+        ClientMetricsSnapshot {
+            requests_total: self.inner.requests_total.load(Ordering::Relaxed),
+            retries_total: self.inner.retries_total.load(Ordering::Relaxed),
+            latency_ms_total: self.inner.latency_ms_total.load(Ordering::Relaxed),
+            prompt_tokens_total: self.inner.prompt_tokens_total.load(Ordering::Relaxed),
+            completion_tokens_total: self.inner.completion_tokens_total.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientMetricsSnapshot {
+    pub requests_total: u64,
+    pub retries_total: u64,
+    pub latency_ms_total: u64,
+    pub prompt_tokens_total: u64,
+    pub completion_tokens_total: u64,
+}
+
+impl ClientMetricsSnapshot {
+    pub fn average_latency_ms(&self) -> f64 {
+        if self.requests_total == 0 {
+            0.0
+        } else {
+            self.latency_ms_total as f64 / self.requests_total as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn average_latency_divides_by_request_count() {
+        let metrics = ClientMetrics::default();
+        metrics.record_request(Duration::from_millis(100), 0);
+        metrics.record_request(Duration::from_millis(300), 1);
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.requests_total, 2);
+        assert_eq!(snapshot.retries_total, 1);
+        assert_eq!(snapshot.average_latency_ms(), 200.0);
+    }
+}
@@ -0,0 +1,44 @@
+//! In-process mock transport for offline tests, so callers don't need a
+//! live OpenAI-compatible backend (or network access at all) to exercise
+//! client code paths. Gated behind the `test-util` feature.
+
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::client::Client;
+use crate::config::OpenAIConfig;
+
+/// Starts a local mock HTTP server and returns a [`Client`] already pointed
+/// at it, along with the `MockServer` handle so the caller can register
+/// expectations on it directly.
+pub async fn mock_client() -> (Client<OpenAIConfig>, MockServer) {
+    let server = MockServer::start().await;
+    let config = OpenAIConfig::new()
+        .with_api_base(server.uri())
+        .with_api_key("test-key");
+    (Client::build(reqwest::Client::new(), config), server)
+}
+
+/// Registers a canned JSON response for `POST <path>` on `server`.
+pub async fn mock_json_post(server: &MockServer, route: &str, body: serde_json::Value) {
+    Mock::given(method("POST"))
+        .and(path(route))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .mount(server)
+        .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn mock_client_returns_canned_response() {
+        let (client, server) = mock_client().await;
+        mock_json_post(&server, "/ping", json!({"ok": true})).await;
+
+        let response: serde_json::Value = client.post("/ping", &json!({})).await.unwrap();
+        assert_eq!(response["ok"], true);
+    }
+}
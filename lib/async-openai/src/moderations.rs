@@ -0,0 +1,60 @@
+//! Moderations endpoint: classify text/image input against content policy
+//! categories before it reaches a model or a user.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::client::Client;
+use crate::config::Config;
+use crate::error::OpenAIError;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateModerationRequest {
+    pub input: ModerationInput,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum ModerationInput {
+    Text(String),
+    Many(Vec<String>),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModerationResult {
+    pub flagged: bool,
+    pub categories: HashMap<String, bool>,
+    pub category_scores: HashMap<String, f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateModerationResponse {
+    pub id: String,
+    pub model: String,
+    pub results: Vec<ModerationResult>,
+}
+
+pub struct Moderations<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Moderations<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    pub async fn create(
+        &self,
+        request: &CreateModerationRequest,
+    ) -> Result<CreateModerationResponse, OpenAIError> {
+        self.client.post("/moderations", request).await
+    }
+}
+
+impl<C: Config> Client<C> {
+    pub fn moderations(&self) -> Moderations<'_, C> {
+        Moderations::new(self)
+    }
+}
@@ -0,0 +1,33 @@
+//! OpenTelemetry instrumentation for outgoing client calls, using the
+//! OpenTelemetry HTTP attribute names (`http.request.method`, `url.path`,
+//! `http.response.status_code`) so events from this crate line up with
+//! the rest of a Dynamo pipeline's traces without extra mapping.
+//!
+//! This only emits `tracing` events (no spans); actually exporting them to an
+//! OTLP collector is up to the binary installing a `tracing-opentelemetry`
+//! layer, same as the rest of Dynamo's components.
+
+use async_trait::async_trait;
+
+use crate::interceptor::Interceptor;
+
+pub struct OtelInterceptor;
+
+#[async_trait]
+impl Interceptor for OtelInterceptor {
+    async fn before_request(&self, request: reqwest::Request) -> reqwest::Request {
+        tracing::info!(
+            http.request.method = %request.method(),
+            url.path = %request.url().path(),
+            "dispatching request"
+        );
+        request
+    }
+
+    async fn after_response(&self, response: &reqwest::Response) {
+        tracing::info!(
+            http.response.status_code = response.status().as_u16(),
+            "received response"
+        );
+    }
+}
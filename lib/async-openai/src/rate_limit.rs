@@ -0,0 +1,75 @@
+//! Rate-limit state parsed from `x-ratelimit-*` response headers, so
+//! callers can back off proactively instead of waiting to hit a 429.
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RateLimitState {
+    pub limit_requests: Option<u64>,
+    pub limit_tokens: Option<u64>,
+    pub remaining_requests: Option<u64>,
+    pub remaining_tokens: Option<u64>,
+    pub reset_requests: Option<std::time::Duration>,
+    pub reset_tokens: Option<std::time::Duration>,
+}
+
+impl RateLimitState {
+    pub fn from_headers(headers: &reqwest::header::HeaderMap) -> Self {
+        Self {
+            limit_requests: header_u64(headers, "x-ratelimit-limit-requests"),
+            limit_tokens: header_u64(headers, "x-ratelimit-limit-tokens"),
+            remaining_requests: header_u64(headers, "x-ratelimit-remaining-requests"),
+            remaining_tokens: header_u64(headers, "x-ratelimit-remaining-tokens"),
+            reset_requests: header_duration(headers, "x-ratelimit-reset-requests"),
+            reset_tokens: header_duration(headers, "x-ratelimit-reset-tokens"),
+        }
+    }
+}
+
+fn header_u64(headers: &reqwest::header::HeaderMap, name: &str) -> Option<u64> {
+    headers.get(name)?.to_str().ok()?.parse().ok()
+}
+
+/// The API expresses resets as durations like `6m0s` or `30s` rather than
+/// absolute timestamps; this parses the subset actually observed in
+/// responses (minutes + seconds).
+fn header_duration(headers: &reqwest::header::HeaderMap, name: &str) -> Option<std::time::Duration> {
+    let raw = headers.get(name)?.to_str().ok()?;
+    parse_go_duration(raw)
+}
+
+fn parse_go_duration(raw: &str) -> Option<std::time::Duration> {
+    let mut total = std::time::Duration::ZERO;
+    let mut num = String::new();
+    for c in raw.chars() {
+        if c.is_ascii_digit() || c == '.' {
+            num.push(c);
+            continue;
+        }
+        let value: f64 = num.parse().ok()?;
+        num.clear();
+        let unit = match c {
+            'h' => 3600.0,
+            'm' => 60.0,
+            's' => 1.0,
+            _ => return None,
+        };
+        total += std::time::Duration::from_secs_f64(value * unit);
+    }
+    Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_minutes_and_seconds() {
+        assert_eq!(
+            parse_go_duration("1m30s"),
+            Some(std::time::Duration::from_secs(90))
+        );
+        assert_eq!(
+            parse_go_duration("45s"),
+            Some(std::time::Duration::from_secs(45))
+        );
+    }
+}
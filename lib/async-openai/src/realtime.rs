@@ -0,0 +1,125 @@
+//! WebSocket client for the OpenAI Realtime API.
+//!
+//! Unlike the rest of this crate, the realtime surface is a persistent
+//! duplex session rather than one request/response: the caller sends
+//! client events (`session.update`, `input_audio_buffer.append`,
+//! `response.create`, ...) and receives a stream of typed server events.
+//! This lets Dynamo components both consume the Realtime API upstream and
+//! proxy it downstream to their own clients without re-parsing raw frames
+//! at each hop.
+
+use futures::{SinkExt, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::config::Config;
+use crate::error::OpenAIError;
+use crate::Client;
+
+/// Client -> server events. Only the handful Dynamo components actually
+/// drive are modeled; unknown/extra fields on the JSON payload are
+/// preserved where practical via `serde_json::Value` leaves.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RealtimeClientEvent {
+    #[serde(rename = "session.update")]
+    SessionUpdate { session: serde_json::Value },
+    #[serde(rename = "input_audio_buffer.append")]
+    InputAudioBufferAppend { audio: String },
+    #[serde(rename = "input_audio_buffer.commit")]
+    InputAudioBufferCommit,
+    #[serde(rename = "response.create")]
+    ResponseCreate,
+}
+
+/// Server -> client events, as received on the websocket.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RealtimeServerEvent {
+    #[serde(rename = "session.created")]
+    SessionCreated {
+        session: serde_json::Value,
+    },
+    #[serde(rename = "session.updated")]
+    SessionUpdated {
+        session: serde_json::Value,
+    },
+    #[serde(rename = "response.created")]
+    ResponseCreated {
+        response: serde_json::Value,
+    },
+    #[serde(rename = "response.audio.delta")]
+    ResponseAudioDelta {
+        delta: String,
+    },
+    #[serde(rename = "response.text.delta")]
+    ResponseTextDelta {
+        delta: String,
+    },
+    #[serde(rename = "response.done")]
+    ResponseDone {
+        response: serde_json::Value,
+    },
+    Error {
+        error: serde_json::Value,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+/// An open Realtime session. Send events with [`RealtimeSession::send`],
+/// and drain [`RealtimeSession::events`] for server events.
+pub struct RealtimeSession {
+    socket: tokio_tungstenite::WebSocketStream<
+        tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+    >,
+}
+
+impl RealtimeSession {
+    pub async fn send(&mut self, event: RealtimeClientEvent) -> Result<(), OpenAIError> {
+        let text = serde_json::to_string(&event).map_err(OpenAIError::JSONDeserialize)?;
+        self.socket
+            .send(Message::Text(text))
+            .await
+            .map_err(|e| OpenAIError::StreamError(e.to_string()))
+    }
+
+    /// Stream of decoded server events; non-text frames are skipped.
+    pub fn events(&mut self) -> impl Stream<Item = Result<RealtimeServerEvent, OpenAIError>> + '_ {
+        self.socket.by_ref().filter_map(|msg| async move {
+            match msg {
+                Ok(Message::Text(text)) => Some(
+                    serde_json::from_str(&text).map_err(OpenAIError::JSONDeserialize),
+                ),
+                Ok(_) => None,
+                Err(e) => Some(Err(OpenAIError::StreamError(e.to_string()))),
+            }
+        })
+    }
+}
+
+impl<C: Config> Client<C> {
+    /// Open a Realtime API session for `model`, authenticated the same way
+    /// as the rest of this client.
+    pub async fn realtime(&self, model: &str) -> Result<RealtimeSession, OpenAIError> {
+        let ws_url = self
+            .config()
+            .url("/realtime")
+            .replacen("https://", "wss://", 1)
+            .replacen("http://", "ws://", 1)
+            + &format!("?model={model}");
+
+        let request = http::Request::builder()
+            .uri(ws_url)
+            .header("Authorization", format!("Bearer {}", self.config().api_key()))
+            .header("OpenAI-Beta", "realtime=v1")
+            .body(())
+            .map_err(|e| OpenAIError::InvalidArgument(e.to_string()))?;
+
+        let (socket, _response) = tokio_tungstenite::connect_async(request)
+            .await
+            .map_err(|e| OpenAIError::StreamError(e.to_string()))?;
+
+        Ok(RealtimeSession { socket })
+    }
+}
@@ -0,0 +1,44 @@
+//! Per-call overrides of client-wide defaults: timeout (embeddings
+//! batches and health probes on the same client want very different
+//! limits), extra headers and query parameters, and an idempotency key.
+
+use std::time::Duration;
+
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions {
+    /// Overrides the client's default total request timeout for this call
+    /// only.
+    pub timeout: Option<Duration>,
+    /// Extra headers added on top of the client's defaults, e.g. a per-call
+    /// `X-Request-Id`. A header the config already sets is not replaced:
+    /// both values are sent.
+    pub extra_headers: Vec<(String, String)>,
+    /// Extra query parameters appended to the request URL.
+    pub extra_query: Vec<(String, String)>,
+    /// Sent as `Idempotency-Key` on mutating requests, so a retried POST
+    /// (whether retried by this client or resent by hand after a timeout)
+    /// is guaranteed to apply at most once server-side.
+    pub idempotency_key: Option<String>,
+}
+
+impl RequestOptions {
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.extra_headers.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn with_query(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.extra_query.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn with_idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.idempotency_key = Some(key.into());
+        self
+    }
+}
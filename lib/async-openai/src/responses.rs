@@ -0,0 +1,58 @@
+//! Responses API: the newer, stateful alternative to Chat Completions that
+//! can carry tool use, reasoning, and multi-turn state server-side.
+
+use serde::{Deserialize, Serialize};
+
+use crate::client::Client;
+use crate::config::Config;
+use crate::error::OpenAIError;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateResponseRequest {
+    pub model: String,
+    pub input: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_response_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<serde_json::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Response {
+    pub id: String,
+    pub object: String,
+    pub status: String,
+    pub output: Vec<serde_json::Value>,
+}
+
+pub struct Responses<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Responses<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    pub async fn create(&self, request: &CreateResponseRequest) -> Result<Response, OpenAIError> {
+        self.client.post("/responses", request).await
+    }
+
+    pub async fn retrieve(&self, response_id: &str) -> Result<Response, OpenAIError> {
+        self.client.get(&format!("/responses/{response_id}")).await
+    }
+
+    pub async fn cancel(&self, response_id: &str) -> Result<Response, OpenAIError> {
+        self.client
+            .post(&format!("/responses/{response_id}/cancel"), &())
+            .await
+    }
+}
+
+impl<C: Config> Client<C> {
+    pub fn responses(&self) -> Responses<'_, C> {
+        Responses::new(self)
+    }
+}
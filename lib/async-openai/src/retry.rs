@@ -0,0 +1,76 @@
+//! Retry policy for transient failures (429, 5xx) with exponential backoff,
+//! honoring the server's `Retry-After` header when present instead of
+//! guessing a delay.
+
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub struct RetryConfig {
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Backoff before attempt `attempt` (0-indexed), doubling each time and
+    /// capped at `max_backoff`.
+    pub fn backoff_for(&self, attempt: u32) -> Duration {
+        let scaled = self.initial_backoff.saturating_mul(1u32 << attempt.min(16));
+        scaled.min(self.max_backoff)
+    }
+
+    pub fn should_retry(&self, attempt: u32, status: reqwest::StatusCode) -> bool {
+        attempt < self.max_retries && (status.as_u16() == 429 || status.is_server_error())
+    }
+}
+
+/// Delay to wait before the next attempt: the server's `Retry-After` value
+/// when given (assumed to be whole seconds, per the OpenAI API), otherwise
+/// the policy's own exponential backoff.
+pub fn retry_after_or_backoff(
+    headers: &reqwest::header::HeaderMap,
+    config: &RetryConfig,
+    attempt: u32,
+) -> Duration {
+    headers
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|s| s.parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or_else(|| config.backoff_for(attempt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_and_caps() {
+        let config = RetryConfig {
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(350),
+        };
+        assert_eq!(config.backoff_for(0), Duration::from_millis(100));
+        assert_eq!(config.backoff_for(1), Duration::from_millis(200));
+        assert_eq!(config.backoff_for(2), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn should_retry_respects_max_attempts() {
+        let config = RetryConfig::default();
+        assert!(config.should_retry(0, reqwest::StatusCode::TOO_MANY_REQUESTS));
+        assert!(!config.should_retry(3, reqwest::StatusCode::TOO_MANY_REQUESTS));
+        assert!(!config.should_retry(0, reqwest::StatusCode::BAD_REQUEST));
+    }
+}
@@ -0,0 +1,138 @@
+//! Server-sent-event streaming helpers shared by every streaming endpoint
+//! (chat completions, responses, realtime fallback over HTTP, ...).
+
+use futures::Stream;
+use serde::de::DeserializeOwned;
+
+use crate::client::Client;
+use crate::config::Config;
+use crate::error::{ApiError, OpenAIError};
+
+/// One decoded SSE payload: either a normal chunk, or an in-band error the
+/// server emitted mid-stream (after headers/status already said 200, so it
+/// can't be surfaced any other way).
+#[derive(Debug)]
+pub enum SseEvent<T> {
+    Data(T),
+    Error(ApiError),
+}
+
+/// Decode one `data: {...}` SSE line, treating a literal `data: [DONE]` as
+/// end-of-stream (`Ok(None)`) and a payload shaped like `{"error": {...}}`
+/// as [`SseEvent::Error`] rather than forcing it through `T`'s Deserialize.
+pub fn decode_event<T: DeserializeOwned>(line: &str) -> Result<Option<SseEvent<T>>, OpenAIError> {
+    let Some(data) = line.strip_prefix("data: ") else {
+        return Ok(None);
+    };
+    if data == "[DONE]" {
+        return Ok(None);
+    }
+
+    let value: serde_json::Value =
+        serde_json::from_str(data).map_err(OpenAIError::JSONDeserialize)?;
+    if let Some(error) = value.get("error") {
+        let api_error: ApiError =
+            serde_json::from_value(error.clone()).map_err(OpenAIError::JSONDeserialize)?;
+        return Ok(Some(SseEvent::Error(api_error)));
+    }
+
+    serde_json::from_value(value)
+        .map(|v| Some(SseEvent::Data(v)))
+        .map_err(OpenAIError::JSONDeserialize)
+}
+
+/// Wraps an SSE byte stream, reconnecting with `Last-Event-ID` when the
+/// underlying connection drops mid-stream instead of surfacing the error
+/// straight to the caller. Each resume (at most `max_resumes`) re-issues
+/// the request, forwarding the id of the last event this stream received.
+pub fn stream_with_resume<C, T>(
+    client: &Client<C>,
+    path: String,
+    body: serde_json::Value,
+    max_resumes: u32,
+) -> impl Stream<Item = Result<T, OpenAIError>> + '_
+where
+    C: Config,
+    T: DeserializeOwned + 'static,
+{
+    async_stream::try_stream! {
+        let mut last_event_id: Option<String> = None;
+        let mut resumes = 0;
+
+        loop {
+            let mut request = client
+                .http()
+                .post(client.config().url(&path))
+                .headers(client.config().headers())
+                .header(
+                    reqwest::header::AUTHORIZATION,
+                    format!("Bearer {}", client.config().api_key()),
+                )
+                .json(&body);
+            if let Some(id) = &last_event_id {
+                request = request.header("Last-Event-ID", id.clone());
+            }
+
+            let response = request.send().await?;
+            let mut byte_stream = response.bytes_stream();
+            let mut buffer = String::new();
+            let mut disconnected = false;
+
+            loop {
+                match futures::StreamExt::next(&mut byte_stream).await {
+                    Some(Ok(chunk)) => {
+                        buffer.push_str(&String::from_utf8_lossy(&chunk));
+                        while let Some(pos) = buffer.find('\n') {
+                            let line = buffer[..pos].trim_end().to_string();
+                            buffer.drain(..=pos);
+                            if let Some(id) = line.strip_prefix("id: ") {
+                                last_event_id = Some(id.to_string());
+                            }
+                            match decode_event::<T>(&line)? {
+                                Some(SseEvent::Data(event)) => yield event,
+                                Some(SseEvent::Error(api_error)) => {
+                                    Err(OpenAIError::ApiError(api_error))?;
+                                }
+                                None => {}
+                            }
+                        }
+                    }
+                    Some(Err(_)) => {
+                        disconnected = true;
+                        break;
+                    }
+                    None => break,
+                }
+            }
+
+            if !disconnected || resumes >= max_resumes {
+                break;
+            }
+            resumes += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Chunk {
+        text: String,
+    }
+
+    #[test]
+    fn decodes_error_payload_distinctly() {
+        let line = r#"data: {"error": {"message": "overloaded", "type": "server_error"}}"#;
+        let event = decode_event::<Chunk>(line).unwrap().unwrap();
+        assert!(matches!(event, SseEvent::Error(e) if e.message == "overloaded"));
+    }
+
+    #[test]
+    fn decodes_data_payload() {
+        let line = r#"data: {"text": "hi"}"#;
+        let event = decode_event::<Chunk>(line).unwrap().unwrap();
+        assert!(matches!(event, SseEvent::Data(c) if c.text == "hi"));
+    }
+}
@@ -0,0 +1,43 @@
+//! Structured outputs: derive a JSON schema straight from a Rust type via
+//! `schemars`, instead of hand-writing the schema that goes into
+//! `response_format` for every request type.
+
+use schemars::JsonSchema;
+use serde_json::Value;
+
+/// Build the `response_format` value for a chat completion request body
+/// that constrains output to `T`'s shape, named `name` per the API's
+/// `json_schema.name` field. `CreateChatCompletionRequest` has no
+/// `response_format` field, so the value goes into a caller-defined
+/// request type (e.g. one sent through `Chat::create_byot`).
+pub fn response_format_for<T: JsonSchema>(name: &str) -> Value {
+    let schema = schemars::schema_for!(T);
+    serde_json::json!({
+        "type": "json_schema",
+        "json_schema": {
+            "name": name,
+            "schema": schema,
+            "strict": true,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(JsonSchema)]
+    struct Weather {
+        #[allow(dead_code)]
+        city: String,
+        #[allow(dead_code)]
+        temp_f: f64,
+    }
+
+    #[test]
+    fn response_format_embeds_schema_name() {
+        let format = response_format_for::<Weather>("weather");
+        assert_eq!(format["json_schema"]["name"], "weather");
+        assert_eq!(format["type"], "json_schema");
+    }
+}
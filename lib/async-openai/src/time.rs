@@ -0,0 +1,14 @@
+//! Sleep abstraction so the retry path works on both native targets and
+//! wasm32, where `tokio::time::sleep` isn't available.
+
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await;
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn sleep(duration: Duration) {
+    gloo_timers::future::sleep(duration).await;
+}
@@ -0,0 +1,72 @@
+//! Token counting integrated directly with request types, so callers can
+//! check a prompt against a model's context window before sending it
+//! rather than discovering the overflow from a 400 response.
+
+use crate::chat::{ChatCompletionRequestMessage, CreateChatCompletionRequest};
+
+/// A pluggable tokenizer (tiktoken-style BPE, SentencePiece, ...). Kept
+/// generic rather than hard-coding tiktoken, since Dynamo deployments
+/// often run non-OpenAI models where token boundaries differ.
+pub trait TokenCounter {
+    fn count(&self, text: &str) -> usize;
+}
+
+/// Rough, tokenizer-free estimate: ~4 bytes/token for English text. Good
+/// enough for a guardrail default when no real tokenizer is wired up.
+pub struct HeuristicTokenCounter;
+
+impl TokenCounter for HeuristicTokenCounter {
+    fn count(&self, text: &str) -> usize {
+        text.len().div_ceil(4).max(1)
+    }
+}
+
+impl CreateChatCompletionRequest {
+    /// Sum of counted tokens across every message's content, plus a small
+    /// per-message overhead for the role/framing tokens real chat
+    /// templates add.
+    pub fn estimate_prompt_tokens(&self, counter: &dyn TokenCounter) -> usize {
+        const PER_MESSAGE_OVERHEAD: usize = 4;
+
+        self.messages
+            .iter()
+            .map(|message| PER_MESSAGE_OVERHEAD + counter.count(message_text(message)))
+            .sum()
+    }
+}
+
+fn message_text(message: &ChatCompletionRequestMessage) -> &str {
+    match message {
+        ChatCompletionRequestMessage::System { content } => content,
+        ChatCompletionRequestMessage::User { content } => content,
+        ChatCompletionRequestMessage::Assistant { content, .. } => {
+            content.as_deref().unwrap_or_default()
+        }
+        ChatCompletionRequestMessage::Tool { content, .. } => content,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimate_includes_overhead_per_message() {
+        let request = CreateChatCompletionRequest {
+            model: "gpt-test".to_string(),
+            messages: vec![
+                ChatCompletionRequestMessage::System {
+                    content: "abcd".to_string(),
+                },
+                ChatCompletionRequestMessage::User {
+                    content: "abcdabcd".to_string(),
+                },
+            ],
+            n: None,
+            stream: None,
+            tools: None,
+        };
+        // (4 overhead + 1 token) + (4 overhead + 2 tokens) = 11
+        assert_eq!(request.estimate_prompt_tokens(&HeuristicTokenCounter), 11);
+    }
+}
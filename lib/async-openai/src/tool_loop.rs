@@ -0,0 +1,72 @@
+//! Round-trip helper for tool use: send a chat request, execute whatever
+//! tool calls come back, append the results as `role: tool` messages, and
+//! resend — repeating until the model stops calling tools or a turn limit
+//! is hit. Saves every caller from re-implementing this loop by hand.
+
+use std::future::Future;
+
+use serde_json::Value;
+
+use crate::chat::{ChatCompletionRequestMessage, Chat, CreateChatCompletionRequest};
+use crate::config::Config;
+use crate::error::OpenAIError;
+
+/// Runs a tool-calling conversation to completion.
+///
+/// `execute` is called once per tool call the model makes, with the
+/// function name and raw JSON arguments, and must return the tool's
+/// result as a string to splice back in as a `role: tool` message.
+pub async fn run_with_tools<C, E, Fut>(
+    chat: &Chat<'_, C>,
+    mut request: CreateChatCompletionRequest,
+    mut execute: E,
+    max_turns: u32,
+) -> Result<CreateChatCompletionRequest, OpenAIError>
+where
+    C: Config,
+    E: FnMut(&str, &Value) -> Fut,
+    Fut: Future<Output = String>,
+{
+    for _ in 0..max_turns {
+        let response = chat.create(&request).await?;
+        let Some(choice) = response.choices.into_iter().next() else {
+            break;
+        };
+
+        let tool_calls = choice.message.tool_calls.unwrap_or_default();
+        if tool_calls.is_empty() {
+            break;
+        }
+
+        request.messages.push(ChatCompletionRequestMessage::Assistant {
+            content: choice.message.content,
+            tool_calls: Some(tool_calls.clone()),
+        });
+
+        for call in &tool_calls {
+            let id = call
+                .get("id")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string();
+            let name = call
+                .get("function")
+                .and_then(|f| f.get("name"))
+                .and_then(|n| n.as_str())
+                .unwrap_or_default();
+            let arguments = call
+                .get("function")
+                .and_then(|f| f.get("arguments"))
+                .cloned()
+                .unwrap_or(Value::Null);
+
+            let result = execute(name, &arguments).await;
+            request.messages.push(ChatCompletionRequestMessage::Tool {
+                content: result,
+                tool_call_id: id,
+            });
+        }
+    }
+
+    Ok(request)
+}
@@ -0,0 +1,94 @@
+//! Helpers for building the underlying `reqwest::Client` with a proxy
+//! and/or custom TLS trust roots, for deployments that sit behind a
+//! corporate proxy or terminate TLS with an internal CA.
+
+use std::path::Path;
+use std::time::Duration;
+
+use crate::error::OpenAIError;
+
+#[derive(Debug, Clone, Default)]
+pub struct TransportOptions {
+    pub proxy_url: Option<String>,
+    /// PEM-encoded CA certificate to trust in addition to the system roots.
+    pub extra_root_cert_pem: Option<Vec<u8>>,
+    /// Disables certificate validation entirely. Only ever set this for a
+    /// local dev endpoint with a self-signed cert — never in production.
+    pub danger_accept_invalid_certs: bool,
+    pub pool: HttpPoolOptions,
+}
+
+/// HTTP/2 connection pool tuning. Defaults match `reqwest`'s own, so
+/// setting `TransportOptions::default()` is a no-op until a deployment
+/// actually needs to tune these (e.g. raising idle connections for a
+/// frontend fanning out to many backend replicas).
+#[derive(Debug, Clone)]
+pub struct HttpPoolOptions {
+    pub pool_max_idle_per_host: usize,
+    pub pool_idle_timeout: Option<Duration>,
+    pub http2_keep_alive_interval: Option<Duration>,
+    pub http2_keep_alive_timeout: Option<Duration>,
+    /// Forces HTTP/2 even over plaintext, for talking to a local engine
+    /// that only speaks h2c.
+    pub http2_prior_knowledge: bool,
+}
+
+impl Default for HttpPoolOptions {
+    fn default() -> Self {
+        Self {
+            pool_max_idle_per_host: usize::MAX,
+            pool_idle_timeout: Some(Duration::from_secs(90)),
+            http2_keep_alive_interval: None,
+            http2_keep_alive_timeout: None,
+            http2_prior_knowledge: false,
+        }
+    }
+}
+
+impl TransportOptions {
+    pub fn with_proxy(mut self, url: impl Into<String>) -> Self {
+        self.proxy_url = Some(url.into());
+        self
+    }
+
+    pub fn with_root_cert_file(mut self, path: impl AsRef<Path>) -> std::io::Result<Self> {
+        self.extra_root_cert_pem = Some(std::fs::read(path)?);
+        Ok(self)
+    }
+}
+
+/// Build the `reqwest::Client` used by [`crate::Client`], applying any
+/// proxy and TLS overrides in `options`.
+pub fn build_http_client(options: &TransportOptions) -> Result<reqwest::Client, OpenAIError> {
+    let mut builder = reqwest::Client::builder()
+        .pool_max_idle_per_host(options.pool.pool_max_idle_per_host);
+
+    if let Some(timeout) = options.pool.pool_idle_timeout {
+        builder = builder.pool_idle_timeout(timeout);
+    }
+    if let Some(interval) = options.pool.http2_keep_alive_interval {
+        builder = builder.http2_keep_alive_interval(interval);
+    }
+    if let Some(timeout) = options.pool.http2_keep_alive_timeout {
+        builder = builder.http2_keep_alive_timeout(timeout);
+    }
+    if options.pool.http2_prior_knowledge {
+        builder = builder.http2_prior_knowledge();
+    }
+
+    if let Some(proxy_url) = &options.proxy_url {
+        let proxy = reqwest::Proxy::all(proxy_url).map_err(OpenAIError::Reqwest)?;
+        builder = builder.proxy(proxy);
+    }
+
+    if let Some(pem) = &options.extra_root_cert_pem {
+        let cert = reqwest::Certificate::from_pem(pem).map_err(OpenAIError::Reqwest)?;
+        builder = builder.add_root_certificate(cert);
+    }
+
+    if options.danger_accept_invalid_certs {
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+
+    builder.build().map_err(OpenAIError::Reqwest)
+}
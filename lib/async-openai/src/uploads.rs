@@ -0,0 +1,101 @@
+//! Uploads API: push large files in sequential parts instead of a single
+//! multipart request, so an interrupted transfer can resume from the last
+//! acknowledged part rather than restarting the whole file.
+
+use serde::{Deserialize, Serialize};
+
+use crate::client::Client;
+use crate::config::Config;
+use crate::error::OpenAIError;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateUploadRequest {
+    pub filename: String,
+    pub purpose: String,
+    pub bytes: u64,
+    pub mime_type: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Upload {
+    pub id: String,
+    pub status: String,
+    pub bytes: u64,
+    pub filename: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UploadPart {
+    pub id: String,
+    pub upload_id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CompleteUploadRequest {
+    pub part_ids: Vec<String>,
+}
+
+pub struct Uploads<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Uploads<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    pub async fn create(&self, request: &CreateUploadRequest) -> Result<Upload, OpenAIError> {
+        self.client.post("/uploads", request).await
+    }
+
+    /// Add one chunk of the file. Callers resuming after a disconnect
+    /// should re-send only the parts whose ids weren't previously
+    /// acknowledged, then call [`Self::complete`] with the full ordered list.
+    pub async fn add_part(
+        &self,
+        upload_id: &str,
+        chunk: Vec<u8>,
+    ) -> Result<UploadPart, OpenAIError> {
+        let form = reqwest::multipart::Form::new().part("data", reqwest::multipart::Part::bytes(chunk));
+        let response = self
+            .client
+            .http()
+            .post(
+                self.client
+                    .config()
+                    .url(&format!("/uploads/{upload_id}/parts")),
+            )
+            .headers(self.client.config().headers())
+            .header(
+                reqwest::header::AUTHORIZATION,
+                format!("Bearer {}", self.client.config().api_key()),
+            )
+            .multipart(form)
+            .send()
+            .await?;
+        let bytes = response.bytes().await?;
+        serde_json::from_slice(&bytes).map_err(OpenAIError::JSONDeserialize)
+    }
+
+    pub async fn complete(
+        &self,
+        upload_id: &str,
+        request: &CompleteUploadRequest,
+    ) -> Result<Upload, OpenAIError> {
+        self.client
+            .post(&format!("/uploads/{upload_id}/complete"), request)
+            .await
+    }
+
+    pub async fn cancel(&self, upload_id: &str) -> Result<Upload, OpenAIError> {
+        self.client
+            .post(&format!("/uploads/{upload_id}/cancel"), &())
+            .await
+    }
+}
+
+impl<C: Config> Client<C> {
+    pub fn uploads(&self) -> Uploads<'_, C> {
+        Uploads::new(self)
+    }
+}
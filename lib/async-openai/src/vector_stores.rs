@@ -0,0 +1,84 @@
+//! Vector stores and file-search: upload files into a managed store and let
+//! the Assistants/Responses API retrieve over them at query time.
+
+use serde::{Deserialize, Serialize};
+
+use crate::client::Client;
+use crate::config::Config;
+use crate::error::OpenAIError;
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct CreateVectorStoreRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_ids: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct VectorStore {
+    pub id: String,
+    pub object: String,
+    pub name: Option<String>,
+    pub status: String,
+    pub file_counts: VectorStoreFileCounts,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct VectorStoreFileCounts {
+    pub in_progress: u32,
+    pub completed: u32,
+    pub failed: u32,
+    pub total: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateVectorStoreFileRequest {
+    pub file_id: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct VectorStoreFile {
+    pub id: String,
+    pub vector_store_id: String,
+    pub status: String,
+}
+
+pub struct VectorStores<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> VectorStores<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    pub async fn create(
+        &self,
+        request: CreateVectorStoreRequest,
+    ) -> Result<VectorStore, OpenAIError> {
+        self.client.post("/vector_stores", &request).await
+    }
+
+    pub async fn retrieve(&self, vector_store_id: &str) -> Result<VectorStore, OpenAIError> {
+        self.client
+            .get(&format!("/vector_stores/{vector_store_id}"))
+            .await
+    }
+
+    pub async fn create_file(
+        &self,
+        vector_store_id: &str,
+        request: CreateVectorStoreFileRequest,
+    ) -> Result<VectorStoreFile, OpenAIError> {
+        self.client
+            .post(&format!("/vector_stores/{vector_store_id}/files"), &request)
+            .await
+    }
+}
+
+impl<C: Config> Client<C> {
+    pub fn vector_stores(&self) -> VectorStores<'_, C> {
+        VectorStores::new(self)
+    }
+}
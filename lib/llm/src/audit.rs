@@ -0,0 +1,255 @@
+//! Audit logging: records who called which model with what parameters
+//! and how the call finished, as structured events a SIEM or log
+//! pipeline can ingest. Prompt and response content is redacted
+//! (hashed or truncated) before an event ever reaches a sink, so audit
+//! logs can be retained and shipped off-box without becoming a second
+//! copy of sensitive request content.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use serde::Serialize;
+
+use crate::tenant::TenantId;
+
+/// How a call ended.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "status")]
+pub enum FinishStatus {
+    Success,
+    Failure { reason: String },
+}
+
+/// How prompt/response content is handled before it's recorded. `Hash`
+/// keeps the event useful for correlating repeated calls (e.g. "this
+/// caller sent the same prompt 500 times") without retaining the
+/// content itself; `Truncate` keeps a short, human-readable prefix for
+/// deployments willing to accept that tradeoff; `None` passes content
+/// through unchanged and should only be used for audit logs treated
+/// with the same sensitivity as raw requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedactionMode {
+    None,
+    Hash,
+    Truncate(usize),
+}
+
+impl RedactionMode {
+    fn apply(&self, content: &str) -> String {
+        match self {
+            RedactionMode::None => content.to_string(),
+            RedactionMode::Hash => {
+                let mut hasher = DefaultHasher::new();
+                content.hash(&mut hasher);
+                format!("sha1-like:{:016x}", hasher.finish())
+            }
+            RedactionMode::Truncate(max_chars) => {
+                if content.chars().count() <= *max_chars {
+                    content.to_string()
+                } else {
+                    let prefix: String = content.chars().take(*max_chars).collect();
+                    format!("{prefix}…")
+                }
+            }
+        }
+    }
+}
+
+/// Which [`RedactionMode`] applies to a call's prompt and response
+/// content. Parameters (sampling settings, model name) are never
+/// redacted — they're assumed non-sensitive and are exactly what an
+/// audit trail needs to be useful.
+#[derive(Debug, Clone, Copy)]
+pub struct AuditRedaction {
+    pub prompt: RedactionMode,
+    pub response: RedactionMode,
+}
+
+impl Default for AuditRedaction {
+    fn default() -> Self {
+        Self {
+            prompt: RedactionMode::Hash,
+            response: RedactionMode::Hash,
+        }
+    }
+}
+
+/// One structured audit record: who called which model with what
+/// parameters, how it finished, and its (redacted) prompt/response.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditEvent {
+    pub subject: String,
+    pub tenant: TenantId,
+    pub model: String,
+    pub parameters: serde_json::Value,
+    pub prompt: String,
+    pub response: String,
+    pub status: FinishStatus,
+    pub timestamp_unix_ms: u128,
+}
+
+/// Where audit events are shipped: a SIEM ingest endpoint, a local
+/// file, or (in tests) an in-memory fake. This crate doesn't vendor a
+/// specific SIEM client, so [`AuditLogger`] is written against this
+/// seam, the same way [`crate::config::ConfigSource`] is written
+/// against a pluggable backend.
+#[async_trait]
+pub trait AuditSink: Send + Sync {
+    async fn record(&self, event: AuditEvent);
+}
+
+/// Builds and emits [`AuditEvent`]s for every call, applying
+/// [`AuditRedaction`] to prompt and response content before handing the
+/// event to an [`AuditSink`].
+pub struct AuditLogger<S> {
+    sink: S,
+    redaction: AuditRedaction,
+}
+
+impl<S: AuditSink> AuditLogger<S> {
+    pub fn new(sink: S, redaction: AuditRedaction) -> Self {
+        Self { sink, redaction }
+    }
+
+    /// Records one completed or failed call.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn record(
+        &self,
+        subject: impl Into<String>,
+        tenant: TenantId,
+        model: impl Into<String>,
+        parameters: serde_json::Value,
+        prompt: &str,
+        response: &str,
+        status: FinishStatus,
+    ) {
+        let event = AuditEvent {
+            subject: subject.into(),
+            tenant,
+            model: model.into(),
+            parameters,
+            prompt: self.redaction.prompt.apply(prompt),
+            response: self.redaction.response.apply(response),
+            status,
+            timestamp_unix_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("system clock is before the Unix epoch")
+                .as_millis(),
+        };
+        self.sink.record(event).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn truncate_leaves_short_content_untouched() {
+        assert_eq!(RedactionMode::Truncate(10).apply("short"), "short");
+    }
+
+    #[test]
+    fn truncate_shortens_long_content_with_an_ellipsis() {
+        assert_eq!(
+            RedactionMode::Truncate(5).apply("this is a long prompt"),
+            "this …"
+        );
+    }
+
+    #[test]
+    fn hash_never_leaks_the_original_content() {
+        let hashed = RedactionMode::Hash.apply("super secret prompt");
+        assert!(!hashed.contains("secret"));
+    }
+
+    #[test]
+    fn hash_is_stable_for_the_same_content() {
+        assert_eq!(
+            RedactionMode::Hash.apply("same prompt"),
+            RedactionMode::Hash.apply("same prompt")
+        );
+    }
+
+    #[test]
+    fn none_mode_passes_content_through() {
+        assert_eq!(RedactionMode::None.apply("verbatim"), "verbatim");
+    }
+
+    #[derive(Default)]
+    struct RecordingSink {
+        events: Mutex<Vec<AuditEvent>>,
+    }
+
+    #[async_trait]
+    impl AuditSink for RecordingSink {
+        async fn record(&self, event: AuditEvent) {
+            self.events.lock().unwrap().push(event);
+        }
+    }
+
+    #[tokio::test]
+    async fn recorded_event_has_redacted_content_and_untouched_parameters() {
+        let sink = RecordingSink::default();
+        let logger = AuditLogger::new(
+            sink,
+            AuditRedaction {
+                prompt: RedactionMode::Hash,
+                response: RedactionMode::Truncate(4),
+            },
+        );
+
+        logger
+            .record(
+                "user-42",
+                TenantId::from("acme-corp"),
+                "llama-3-70b",
+                serde_json::json!({"temperature": 0.0}),
+                "what is the capital of France",
+                "Paris is the capital of France.",
+                FinishStatus::Success,
+            )
+            .await;
+
+        let events = logger.sink.events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.subject, "user-42");
+        assert_eq!(event.model, "llama-3-70b");
+        assert_eq!(event.parameters, serde_json::json!({"temperature": 0.0}));
+        assert!(!event.prompt.contains("capital"));
+        assert_eq!(event.response, "Pari…");
+        assert_eq!(event.status, FinishStatus::Success);
+    }
+
+    #[tokio::test]
+    async fn a_failed_call_records_its_failure_reason() {
+        let sink = RecordingSink::default();
+        let logger = AuditLogger::new(sink, AuditRedaction::default());
+
+        logger
+            .record(
+                "user-7",
+                TenantId::from("acme-corp"),
+                "llama-3-8b",
+                serde_json::json!({}),
+                "prompt",
+                "",
+                FinishStatus::Failure {
+                    reason: "upstream timeout".to_string(),
+                },
+            )
+            .await;
+
+        let events = logger.sink.events.lock().unwrap();
+        assert_eq!(
+            events[0].status,
+            FinishStatus::Failure {
+                reason: "upstream timeout".to_string()
+            }
+        );
+    }
+}
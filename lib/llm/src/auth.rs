@@ -0,0 +1,417 @@
+//! Authentication for the OpenAI-compatible HTTP frontend: validates an
+//! incoming request's bearer credential against a pluggable [`KeyStore`],
+//! then attaches the resolved [`Principal`] (model allowlist,
+//! rate-limit tier) to the request so downstream handlers can see who's
+//! calling without re-parsing credentials. Enforcing the rate-limit tier
+//! itself is left to whatever admits the request (e.g.
+//! `dynamo_runtime::admission`); this module only resolves which tier a
+//! caller is in.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Json, Response};
+use serde::Serialize;
+
+use crate::tenant::TenantId;
+
+/// Which rate-limit tier a key is assigned to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitTier {
+    Free,
+    Standard,
+    Unlimited,
+}
+
+/// Which models a key may call.
+#[derive(Debug, Clone)]
+pub enum ModelAllowlist {
+    All,
+    Only(Vec<String>),
+}
+
+impl ModelAllowlist {
+    pub fn permits(&self, model: &str) -> bool {
+        match self {
+            ModelAllowlist::All => true,
+            ModelAllowlist::Only(models) => models.iter().any(|m| m == model),
+        }
+    }
+}
+
+/// The authenticated caller behind a request: which key or subject
+/// authenticated it, which tenant it's billed and quota-limited under,
+/// which models it may call, and its rate-limit tier.
+#[derive(Debug, Clone)]
+pub struct Principal {
+    pub subject: String,
+    pub tenant: TenantId,
+    pub allowed_models: ModelAllowlist,
+    pub tier: RateLimitTier,
+}
+
+/// Resolves the [`Principal`] behind a bearer credential extracted from
+/// a request. Implementations range from an in-memory table of static
+/// keys to a call out to an external validation service;
+/// [`require_auth`] is generic over this so swapping one in for another
+/// doesn't touch the middleware itself.
+#[async_trait]
+pub trait KeyStore: Send + Sync {
+    async fn authenticate(&self, credential: &str) -> Option<Principal>;
+}
+
+/// A [`KeyStore`] backed by a fixed, in-memory table of API keys — the
+/// key material for a config-file-driven deployment, loaded once at
+/// startup.
+#[derive(Default)]
+pub struct StaticKeyStore {
+    keys: HashMap<String, Principal>,
+}
+
+impl StaticKeyStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, api_key: impl Into<String>, principal: Principal) -> &mut Self {
+        self.keys.insert(api_key.into(), principal);
+        self
+    }
+}
+
+#[async_trait]
+impl KeyStore for StaticKeyStore {
+    async fn authenticate(&self, credential: &str) -> Option<Principal> {
+        self.keys.get(credential).cloned()
+    }
+}
+
+/// A [`KeyStore`] for deployments that sit behind a trusted gateway
+/// which has already authenticated the caller and forwards the result
+/// as the bearer credential (e.g. a signed identity string): trusts the
+/// credential as a subject name and looks up its policy, rather than
+/// re-validating a secret.
+#[derive(Default)]
+pub struct HeaderForwardedKeyStore {
+    policies: HashMap<String, (TenantId, ModelAllowlist, RateLimitTier)>,
+}
+
+impl HeaderForwardedKeyStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_policy(
+        &mut self,
+        subject: impl Into<String>,
+        tenant: TenantId,
+        allowed_models: ModelAllowlist,
+        tier: RateLimitTier,
+    ) -> &mut Self {
+        self.policies
+            .insert(subject.into(), (tenant, allowed_models, tier));
+        self
+    }
+}
+
+#[async_trait]
+impl KeyStore for HeaderForwardedKeyStore {
+    async fn authenticate(&self, credential: &str) -> Option<Principal> {
+        let (tenant, allowed_models, tier) = self.policies.get(credential)?.clone();
+        Some(Principal {
+            subject: credential.to_string(),
+            tenant,
+            allowed_models,
+            tier,
+        })
+    }
+}
+
+/// The remote call a third-party entitlement service needs to support
+/// for [`ExternalValidatorKeyStore`] to validate a credential against
+/// it. No specific vendor's client lives in this crate, so this trait
+/// is the seam a real integration is written against.
+#[async_trait]
+pub trait ExternalAuthValidator: Send + Sync {
+    async fn validate(&self, credential: &str) -> Option<Principal>;
+}
+
+/// A [`KeyStore`] that defers every lookup to an external entitlement
+/// service, for deployments that manage API keys outside Dynamo
+/// entirely.
+pub struct ExternalValidatorKeyStore<V> {
+    validator: V,
+}
+
+impl<V: ExternalAuthValidator> ExternalValidatorKeyStore<V> {
+    pub fn new(validator: V) -> Self {
+        Self { validator }
+    }
+}
+
+#[async_trait]
+impl<V: ExternalAuthValidator> KeyStore for ExternalValidatorKeyStore<V> {
+    async fn authenticate(&self, credential: &str) -> Option<Principal> {
+        self.validator.validate(credential).await
+    }
+}
+
+/// Decoded claims off a JWT's payload segment, the subset this crate
+/// cares about.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct JwtClaims {
+    pub sub: String,
+    #[serde(default)]
+    pub tenant: Option<String>,
+    #[serde(default)]
+    pub models: Vec<String>,
+    #[serde(default)]
+    pub tier: Option<String>,
+}
+
+/// Verifies a JWT's signature and expiry, returning its claims if it's
+/// valid. No JWT/crypto crate lives in this tree, so this trait is the
+/// seam a real verifier (RS256 via a signing-key set, or a call to an
+/// IdP's introspection endpoint) is written against.
+pub trait JwtVerifier: Send + Sync {
+    fn verify(&self, token: &str) -> Option<JwtClaims>;
+}
+
+fn tier_from_claim(tier: Option<&str>) -> RateLimitTier {
+    match tier {
+        Some("unlimited") => RateLimitTier::Unlimited,
+        Some("standard") => RateLimitTier::Standard,
+        _ => RateLimitTier::Free,
+    }
+}
+
+/// A [`KeyStore`] that treats the bearer credential as a JWT, verifying
+/// it via a pluggable [`JwtVerifier`] and mapping its claims to a
+/// [`Principal`].
+pub struct JwtKeyStore<V> {
+    verifier: V,
+}
+
+impl<V: JwtVerifier> JwtKeyStore<V> {
+    pub fn new(verifier: V) -> Self {
+        Self { verifier }
+    }
+}
+
+#[async_trait]
+impl<V: JwtVerifier> KeyStore for JwtKeyStore<V> {
+    async fn authenticate(&self, credential: &str) -> Option<Principal> {
+        let claims = self.verifier.verify(credential)?;
+        let allowed_models = if claims.models.is_empty() {
+            ModelAllowlist::All
+        } else {
+            ModelAllowlist::Only(claims.models)
+        };
+        let tenant = TenantId(claims.tenant.unwrap_or_else(|| claims.sub.clone()));
+        Some(Principal {
+            subject: claims.sub,
+            tenant,
+            allowed_models,
+            tier: tier_from_claim(claims.tier.as_deref()),
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct AuthErrorBody {
+    error: AuthErrorDetail,
+}
+
+#[derive(Debug, Serialize)]
+struct AuthErrorDetail {
+    message: String,
+    code: &'static str,
+}
+
+fn auth_error(status: StatusCode, code: &'static str, message: impl Into<String>) -> Response {
+    (
+        status,
+        Json(AuthErrorBody {
+            error: AuthErrorDetail {
+                message: message.into(),
+                code,
+            },
+        }),
+    )
+        .into_response()
+}
+
+fn bearer_token(req: &Request) -> Option<&str> {
+    req.headers()
+        .get(axum::http::header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+}
+
+/// Best-effort peek at the request body's `model` field, for checking a
+/// principal's allowlist. Buffers the whole body so it can be parsed and
+/// then put back for the handler to read; requests whose body isn't a
+/// JSON object with a string `model` field are let through unchecked,
+/// since not every OpenAI-compatible route takes a model in the body.
+async fn peek_model(req: Request) -> (Request, Option<String>) {
+    let (parts, body) = req.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(_) => return (Request::from_parts(parts, Body::empty()), None),
+    };
+    let model = serde_json::from_slice::<serde_json::Value>(&bytes)
+        .ok()
+        .and_then(|v| v.get("model")?.as_str().map(str::to_string));
+    (Request::from_parts(parts, Body::from(bytes)), model)
+}
+
+/// Axum middleware: requires a valid bearer credential, resolves it to a
+/// [`Principal`] via `store`, checks the principal's model allowlist
+/// against the request body's `model` field when present, and inserts
+/// the `Principal` into the request's extensions for handlers to read.
+pub async fn require_auth<S: KeyStore + 'static>(
+    State(store): State<std::sync::Arc<S>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let Some(token) = bearer_token(&req) else {
+        return auth_error(
+            StatusCode::UNAUTHORIZED,
+            "missing_api_key",
+            "missing bearer credential",
+        );
+    };
+    let token = token.to_string();
+
+    let Some(principal) = store.authenticate(&token).await else {
+        return auth_error(
+            StatusCode::UNAUTHORIZED,
+            "invalid_api_key",
+            "invalid bearer credential",
+        );
+    };
+
+    let (mut req, model) = peek_model(req).await;
+    if let Some(model) = model {
+        if !principal.allowed_models.permits(&model) {
+            return auth_error(
+                StatusCode::FORBIDDEN,
+                "model_not_allowed",
+                format!("key is not permitted to call model {model}"),
+            );
+        }
+    }
+
+    req.extensions_mut().insert(principal);
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allowlist_all_permits_any_model() {
+        assert!(ModelAllowlist::All.permits("llama-3"));
+    }
+
+    #[test]
+    fn allowlist_only_permits_listed_models() {
+        let allowlist = ModelAllowlist::Only(vec!["llama-3".to_string()]);
+        assert!(allowlist.permits("llama-3"));
+        assert!(!allowlist.permits("mistral"));
+    }
+
+    #[tokio::test]
+    async fn static_key_store_authenticates_known_key() {
+        let mut store = StaticKeyStore::new();
+        store.insert(
+            "sk-test",
+            Principal {
+                subject: "acme-corp".to_string(),
+                tenant: TenantId::from("acme-corp"),
+                allowed_models: ModelAllowlist::All,
+                tier: RateLimitTier::Standard,
+            },
+        );
+        let principal = store.authenticate("sk-test").await.unwrap();
+        assert_eq!(principal.subject, "acme-corp");
+        assert!(store.authenticate("sk-unknown").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn header_forwarded_store_resolves_policy_by_subject() {
+        let mut store = HeaderForwardedKeyStore::new();
+        store.set_policy(
+            "user:alice",
+            TenantId::from("acme-corp"),
+            ModelAllowlist::All,
+            RateLimitTier::Unlimited,
+        );
+        let principal = store.authenticate("user:alice").await.unwrap();
+        assert_eq!(principal.tier, RateLimitTier::Unlimited);
+        assert!(store.authenticate("user:bob").await.is_none());
+    }
+
+    struct FakeValidator {
+        valid_credential: &'static str,
+    }
+
+    #[async_trait]
+    impl ExternalAuthValidator for FakeValidator {
+        async fn validate(&self, credential: &str) -> Option<Principal> {
+            if credential == self.valid_credential {
+                Some(Principal {
+                    subject: "remote-subject".to_string(),
+                    tenant: TenantId::from("remote-subject"),
+                    allowed_models: ModelAllowlist::All,
+                    tier: RateLimitTier::Free,
+                })
+            } else {
+                None
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn external_validator_store_delegates_to_validator() {
+        let store = ExternalValidatorKeyStore::new(FakeValidator {
+            valid_credential: "token-123",
+        });
+        assert!(store.authenticate("token-123").await.is_some());
+        assert!(store.authenticate("token-456").await.is_none());
+    }
+
+    struct FakeJwtVerifier;
+
+    impl JwtVerifier for FakeJwtVerifier {
+        fn verify(&self, token: &str) -> Option<JwtClaims> {
+            if token == "valid.jwt.token" {
+                Some(JwtClaims {
+                    sub: "user-42".to_string(),
+                    tenant: Some("acme-corp".to_string()),
+                    models: vec!["llama-3".to_string()],
+                    tier: Some("unlimited".to_string()),
+                })
+            } else {
+                None
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn jwt_key_store_maps_claims_to_principal() {
+        let store = JwtKeyStore::new(FakeJwtVerifier);
+        let principal = store.authenticate("valid.jwt.token").await.unwrap();
+        assert_eq!(principal.subject, "user-42");
+        assert_eq!(principal.tier, RateLimitTier::Unlimited);
+        assert!(principal.allowed_models.permits("llama-3"));
+        assert!(!principal.allowed_models.permits("mistral"));
+        assert!(store.authenticate("garbage").await.is_none());
+    }
+}
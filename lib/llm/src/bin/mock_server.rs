@@ -0,0 +1,22 @@
+//! Standalone OpenAI-compatible mock server: a thin binary wrapping
+//! [`dynamo_llm::mocker::server`] so client tooling, gateways, and load
+//! tests can run against a GPU-free endpoint that behaves like a loaded
+//! Dynamo worker.
+
+use dynamo_llm::mocker::engine::MockEngine;
+use dynamo_llm::mocker::server::{router, ServerState};
+
+#[tokio::main]
+async fn main() {
+    let addr = std::env::var("DYNAMO_MOCKER_ADDR").unwrap_or_else(|_| "127.0.0.1:8000".to_string());
+    let engine = MockEngine::with_default_profile();
+    let app = router(ServerState::new(engine));
+
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .unwrap_or_else(|e| panic!("failed to bind {addr}: {e}"));
+    println!("dynamo mock server listening on {addr}");
+    axum::serve(listener, app)
+        .await
+        .unwrap_or_else(|e| panic!("mock server exited: {e}"));
+}
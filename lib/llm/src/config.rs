@@ -0,0 +1,331 @@
+//! Hot-reloadable frontend configuration: router policy, model aliases,
+//! per-model rate limits, and tool-call parser selection, applied
+//! atomically at runtime from an etcd key or a watched file instead of
+//! requiring a restart. A rejected change (malformed or failing
+//! validation) leaves the previously-applied config live.
+
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// Which load-balancing policy the push router should use. Mirrors the
+/// strategies in [`crate::kv_router::strategy`], plus the KV-aware
+/// router itself, as a config-friendly name rather than a trait object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RouterPolicy {
+    KvAware,
+    RoundRobin,
+    LeastOutstandingRequests,
+    PowerOfTwoChoices,
+}
+
+/// Maps a client-facing model name to the name the backend actually
+/// serves, so aliases can be renamed without every caller updating at
+/// once.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelAlias {
+    pub alias: String,
+    pub target_model: String,
+}
+
+/// Per-model override of the defaults an [`crate::auth`] policy would
+/// otherwise apply.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RateLimitOverride {
+    pub requests_per_second: f64,
+    pub burst: u32,
+}
+
+/// The full hot-reloadable surface for one frontend.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrontendConfig {
+    pub router_policy: RouterPolicy,
+    pub model_aliases: Vec<ModelAlias>,
+    #[serde(default)]
+    pub rate_limits: std::collections::HashMap<String, RateLimitOverride>,
+    /// Name of the tool-call parser to use, e.g. `"hermes"` or
+    /// `"mistral"`; must be one of the names the caller knows about.
+    pub parser: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("malformed config: {0}")]
+    Malformed(String),
+    #[error("duplicate model alias {0:?}")]
+    DuplicateAlias(String),
+    #[error("unknown parser {0:?}")]
+    UnknownParser(String),
+    #[error("invalid rate limit for model {model:?}: {reason}")]
+    InvalidRateLimit { model: String, reason: String },
+    #[error("config source failed: {0}")]
+    Source(String),
+}
+
+impl FrontendConfig {
+    /// Checks invariants a parsed config must hold before it's safe to
+    /// swap in: no two aliases for the same name, every rate limit
+    /// makes sense, and the chosen parser is one `known_parsers` lists.
+    pub fn validate(&self, known_parsers: &[&str]) -> Result<(), ConfigError> {
+        let mut seen = std::collections::HashSet::new();
+        for alias in &self.model_aliases {
+            if !seen.insert(alias.alias.as_str()) {
+                return Err(ConfigError::DuplicateAlias(alias.alias.clone()));
+            }
+        }
+
+        for (model, limit) in &self.rate_limits {
+            if limit.requests_per_second <= 0.0 {
+                return Err(ConfigError::InvalidRateLimit {
+                    model: model.clone(),
+                    reason: "requests_per_second must be positive".to_string(),
+                });
+            }
+            if limit.burst == 0 {
+                return Err(ConfigError::InvalidRateLimit {
+                    model: model.clone(),
+                    reason: "burst must be nonzero".to_string(),
+                });
+            }
+        }
+
+        if !known_parsers.contains(&self.parser.as_str()) {
+            return Err(ConfigError::UnknownParser(self.parser.clone()));
+        }
+
+        Ok(())
+    }
+}
+
+/// The current config, swapped atomically on every accepted reload.
+/// Cheap to read: [`ConfigHandle::load`] only clones an `Arc`.
+#[derive(Default)]
+pub struct ConfigHandle {
+    current: RwLock<Option<Arc<FrontendConfig>>>,
+}
+
+impl ConfigHandle {
+    pub fn new(initial: FrontendConfig) -> Self {
+        Self {
+            current: RwLock::new(Some(Arc::new(initial))),
+        }
+    }
+
+    pub fn load(&self) -> Arc<FrontendConfig> {
+        self.current
+            .read()
+            .unwrap()
+            .clone()
+            .expect("ConfigHandle read before any config was loaded")
+    }
+
+    fn store(&self, config: FrontendConfig) {
+        *self.current.write().unwrap() = Some(Arc::new(config));
+    }
+}
+
+/// Where hot-reloadable config is read from: an etcd key, a watched
+/// file, or (in tests) an in-memory fake. This crate doesn't vendor an
+/// etcd client or a filesystem-watch crate, so [`ConfigWatcher`] is
+/// written against this seam, the same way `dynamo_runtime`'s etcd
+/// discovery backend is written against `EtcdClient`.
+#[async_trait]
+pub trait ConfigSource: Send + Sync {
+    /// Blocks until a new config value is available, then returns it
+    /// as raw JSON for [`ConfigWatcher`] to parse and validate. Returns
+    /// `Err` only when the source itself has failed (e.g. the etcd
+    /// watch stream or file watcher died), not for a malformed value —
+    /// that's surfaced from parsing instead so the watch loop can keep
+    /// running.
+    async fn next_change(&self) -> Result<serde_json::Value, ConfigError>;
+}
+
+/// Watches a [`ConfigSource`] and atomically applies every change that
+/// parses and validates, onto a shared [`ConfigHandle`].
+pub struct ConfigWatcher<S> {
+    source: S,
+    handle: Arc<ConfigHandle>,
+    known_parsers: Vec<String>,
+}
+
+impl<S: ConfigSource> ConfigWatcher<S> {
+    pub fn new(source: S, handle: Arc<ConfigHandle>, known_parsers: Vec<String>) -> Self {
+        Self {
+            source,
+            handle,
+            known_parsers,
+        }
+    }
+
+    fn parse_and_validate(&self, raw: serde_json::Value) -> Result<FrontendConfig, ConfigError> {
+        let config: FrontendConfig =
+            serde_json::from_value(raw).map_err(|err| ConfigError::Malformed(err.to_string()))?;
+        let known: Vec<&str> = self.known_parsers.iter().map(String::as_str).collect();
+        config.validate(&known)?;
+        Ok(config)
+    }
+
+    /// Applies `raw` once: parses, validates, and swaps it in on
+    /// success. Returns the validation error (without touching the
+    /// live config) on failure.
+    pub fn apply(&self, raw: serde_json::Value) -> Result<(), ConfigError> {
+        let config = self.parse_and_validate(raw)?;
+        self.handle.store(config);
+        Ok(())
+    }
+
+    /// Runs until `source.next_change()` reports the source itself has
+    /// failed, applying every change that validates and silently
+    /// keeping the previous config live for anything that doesn't.
+    /// Meant to be driven by a single long-lived `tokio::spawn`ed task.
+    pub async fn run(&self) -> ConfigError {
+        loop {
+            match self.source.next_change().await {
+                Ok(raw) => {
+                    let _ = self.apply(raw);
+                }
+                Err(err) => return err,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    fn valid_config() -> FrontendConfig {
+        FrontendConfig {
+            router_policy: RouterPolicy::KvAware,
+            model_aliases: vec![ModelAlias {
+                alias: "gpt-4".to_string(),
+                target_model: "llama-3-70b".to_string(),
+            }],
+            rate_limits: std::collections::HashMap::new(),
+            parser: "hermes".to_string(),
+        }
+    }
+
+    #[test]
+    fn valid_config_passes() {
+        assert!(valid_config().validate(&["hermes", "mistral"]).is_ok());
+    }
+
+    #[test]
+    fn duplicate_alias_is_rejected() {
+        let mut config = valid_config();
+        config.model_aliases.push(ModelAlias {
+            alias: "gpt-4".to_string(),
+            target_model: "llama-3-8b".to_string(),
+        });
+        assert!(matches!(
+            config.validate(&["hermes"]),
+            Err(ConfigError::DuplicateAlias(alias)) if alias == "gpt-4"
+        ));
+    }
+
+    #[test]
+    fn unknown_parser_is_rejected() {
+        let config = valid_config();
+        assert!(matches!(
+            config.validate(&["mistral"]),
+            Err(ConfigError::UnknownParser(parser)) if parser == "hermes"
+        ));
+    }
+
+    #[test]
+    fn nonpositive_rate_limit_is_rejected() {
+        let mut config = valid_config();
+        config.rate_limits.insert(
+            "llama-3-70b".to_string(),
+            RateLimitOverride {
+                requests_per_second: 0.0,
+                burst: 10,
+            },
+        );
+        assert!(matches!(
+            config.validate(&["hermes"]),
+            Err(ConfigError::InvalidRateLimit { model, .. }) if model == "llama-3-70b"
+        ));
+    }
+
+    #[test]
+    fn config_handle_load_reflects_the_last_store() {
+        let handle = ConfigHandle::new(valid_config());
+        assert_eq!(handle.load().parser, "hermes");
+
+        let mut updated = valid_config();
+        updated.parser = "mistral".to_string();
+        handle.store(updated);
+        assert_eq!(handle.load().parser, "mistral");
+    }
+
+    #[derive(Default)]
+    struct FakeConfigSource {
+        changes: Mutex<VecDeque<Result<serde_json::Value, ConfigError>>>,
+    }
+
+    #[async_trait]
+    impl ConfigSource for FakeConfigSource {
+        async fn next_change(&self) -> Result<serde_json::Value, ConfigError> {
+            self.changes
+                .lock()
+                .unwrap()
+                .pop_front()
+                .unwrap_or_else(|| Err(ConfigError::Source("no more changes".to_string())))
+        }
+    }
+
+    #[tokio::test]
+    async fn watcher_applies_a_valid_change() {
+        let handle = Arc::new(ConfigHandle::new(valid_config()));
+        let mut updated = valid_config();
+        updated.parser = "mistral".to_string();
+        let source = FakeConfigSource {
+            changes: Mutex::new(VecDeque::from([
+                Ok(serde_json::to_value(&updated).unwrap()),
+            ])),
+        };
+        let watcher = ConfigWatcher::new(source, handle.clone(), vec!["mistral".to_string()]);
+
+        watcher.run().await;
+        assert_eq!(handle.load().parser, "mistral");
+    }
+
+    #[tokio::test]
+    async fn watcher_keeps_the_live_config_when_a_change_fails_validation() {
+        let handle = Arc::new(ConfigHandle::new(valid_config()));
+        let mut invalid = valid_config();
+        invalid.parser = "unknown-parser".to_string();
+        let source = FakeConfigSource {
+            changes: Mutex::new(VecDeque::from([
+                Ok(serde_json::to_value(&invalid).unwrap()),
+            ])),
+        };
+        let watcher = ConfigWatcher::new(source, handle.clone(), vec!["hermes".to_string()]);
+
+        watcher.run().await;
+        assert_eq!(handle.load().parser, "hermes");
+    }
+
+    #[tokio::test]
+    async fn watcher_applies_multiple_changes_in_order() {
+        let handle = Arc::new(ConfigHandle::new(valid_config()));
+        let mut second = valid_config();
+        second.router_policy = RouterPolicy::RoundRobin;
+        let source = FakeConfigSource {
+            changes: Mutex::new(VecDeque::from([
+                Ok(serde_json::to_value(valid_config()).unwrap()),
+                Ok(serde_json::to_value(&second).unwrap()),
+            ])),
+        };
+        let watcher = ConfigWatcher::new(source, handle.clone(), vec!["hermes".to_string()]);
+
+        watcher.run().await;
+        assert_eq!(handle.load().router_policy, RouterPolicy::RoundRobin);
+    }
+}
@@ -0,0 +1,16 @@
+//! KV-aware request routing: track which blocks each worker has
+//! resident via their KV cache events, and pick a worker for a new
+//! request by combining that overlap with load.
+
+pub mod radix;
+pub mod router;
+pub mod sticky;
+pub mod strategy;
+
+pub use radix::RadixTree;
+pub use router::{KvAwareRouter, KvRouterConfig, PushRouter};
+pub use sticky::StickyRouter;
+pub use strategy::{
+    LeastOutstandingRequests, LoadBalancingStrategy, PowerOfTwoChoices, RoundRobin, StrategyRouter,
+    WorkerLoad,
+};
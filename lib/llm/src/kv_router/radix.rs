@@ -0,0 +1,93 @@
+//! A radix tree over chained block hashes (see
+//! [`crate::mocker::prefix_cache::hash_blocks`] for how the chain is
+//! built), used to find which workers already have how much of a
+//! request's prefix cached without re-tokenizing or re-hashing on the
+//! router side.
+
+use std::collections::{HashMap, HashSet};
+
+#[derive(Default)]
+struct Node {
+    children: HashMap<u64, Node>,
+    workers: HashSet<String>,
+}
+
+/// Per-worker cached-block-chain membership, keyed by the same chained
+/// block hashes workers advertise in their KV events.
+#[derive(Default)]
+pub struct RadixTree {
+    root: Node,
+}
+
+impl RadixTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that `worker_id` has `block_hashes` resident, in chain
+    /// order.
+    pub fn insert(&mut self, block_hashes: &[u64], worker_id: &str) {
+        let mut node = &mut self.root;
+        for hash in block_hashes {
+            node = node.children.entry(*hash).or_default();
+            node.workers.insert(worker_id.to_string());
+        }
+    }
+
+    /// Records that `worker_id` no longer has `block_hashes` resident.
+    /// Stops at the first hash not on the tree rather than inserting it.
+    pub fn remove(&mut self, block_hashes: &[u64], worker_id: &str) {
+        let mut node = &mut self.root;
+        for hash in block_hashes {
+            match node.children.get_mut(hash) {
+                Some(child) => {
+                    child.workers.remove(worker_id);
+                    node = child;
+                }
+                None => return,
+            }
+        }
+    }
+
+    /// For each worker that has at least one leading block of
+    /// `block_hashes` cached, how many leading blocks (a contiguous
+    /// prefix match, since the chain breaks the walk on first miss).
+    pub fn matched_blocks(&self, block_hashes: &[u64]) -> HashMap<String, u32> {
+        let mut matched = HashMap::new();
+        let mut node = &self.root;
+        for (depth, hash) in block_hashes.iter().enumerate() {
+            match node.children.get(hash) {
+                Some(child) => {
+                    for worker in &child.workers {
+                        matched.insert(worker.clone(), depth as u32 + 1);
+                    }
+                    node = child;
+                }
+                None => break,
+            }
+        }
+        matched
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matched_blocks_stops_at_first_chain_break() {
+        let mut tree = RadixTree::new();
+        tree.insert(&[1, 2, 3], "worker-a");
+        let matched = tree.matched_blocks(&[1, 2, 9]);
+        assert_eq!(matched.get("worker-a"), Some(&2));
+    }
+
+    #[test]
+    fn remove_clears_worker_membership() {
+        let mut tree = RadixTree::new();
+        tree.insert(&[1, 2], "worker-a");
+        tree.remove(&[1, 2], "worker-a");
+        let matched = tree.matched_blocks(&[1, 2]);
+        assert!(matched.is_empty());
+    }
+}
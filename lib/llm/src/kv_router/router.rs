@@ -0,0 +1,267 @@
+//! KV-aware worker selection: combine prefix overlap (from the
+//! [`RadixTree`]) with reported load to decide which worker a request
+//! should be pushed to.
+
+use std::collections::HashMap;
+
+use crate::mocker::kv_events::KvCacheEvent;
+
+use super::radix::RadixTree;
+
+/// The decision surface a KV-aware router exposes to callers. This
+/// mirrors the push-router interface the pipeline crate would define,
+/// but that crate doesn't exist in this tree yet, so it's declared here
+/// until routing moves up into a shared crate.
+pub trait PushRouter {
+    /// Picks the worker to push a request with this token-block-hash
+    /// chain to, or `None` if no workers are known yet.
+    fn select_worker(&mut self, token_block_hashes: &[u64]) -> Option<String>;
+
+    /// Whether `worker_id` is still a good pick for traffic that wants
+    /// to stick to it (see [`super::sticky::StickyRouter`]). Routers
+    /// that don't track per-worker health can leave the default of
+    /// "always available".
+    fn is_worker_available(&self, _worker_id: &str) -> bool {
+        true
+    }
+}
+
+/// Relative weight of cache overlap vs load when scoring a worker.
+/// Higher `overlap_weight` favors prefix reuse at the cost of load
+/// balance; higher `load_weight` does the opposite.
+#[derive(Debug, Clone)]
+pub struct KvRouterConfig {
+    pub overlap_weight: f64,
+    pub load_weight: f64,
+    /// Load above which a worker is considered overloaded for the
+    /// purposes of [`KvAwareRouter::is_available`], rather than merely
+    /// a less attractive scoring candidate.
+    pub overload_threshold: f64,
+}
+
+impl Default for KvRouterConfig {
+    fn default() -> Self {
+        Self {
+            overlap_weight: 1.0,
+            load_weight: 1.0,
+            overload_threshold: f64::INFINITY,
+        }
+    }
+}
+
+/// Consumes KV cache events from workers to track which blocks each one
+/// has resident, and scores candidates for an incoming request by
+/// combining that overlap with each worker's last-reported load.
+pub struct KvAwareRouter {
+    config: KvRouterConfig,
+    tree: RadixTree,
+    load: HashMap<String, f64>,
+    not_ready: HashMap<String, bool>,
+}
+
+impl KvAwareRouter {
+    pub fn new(config: KvRouterConfig) -> Self {
+        Self {
+            config,
+            tree: RadixTree::new(),
+            load: HashMap::new(),
+            not_ready: HashMap::new(),
+        }
+    }
+
+    /// Marks a worker ready or not ready, normally driven by the
+    /// worker's own liveness/readiness probes (engine loaded, KV pool
+    /// initialized, event-plane connected) rather than by request
+    /// failures. A worker that has never reported is assumed ready, so
+    /// routing isn't blocked on every worker wiring up probes at once.
+    pub fn update_readiness(&mut self, worker_id: &str, ready: bool) {
+        self.not_ready.insert(worker_id.to_string(), !ready);
+    }
+
+    fn is_ready(&self, worker_id: &str) -> bool {
+        !self.not_ready.get(worker_id).copied().unwrap_or(false)
+    }
+
+    /// Whether `worker_id` is ready and reporting load at or below
+    /// [`KvRouterConfig::overload_threshold`]. Used by
+    /// [`super::sticky::StickyRouter`] to decide whether a session's
+    /// previously-chosen worker is still a good pick, without it
+    /// needing to know about overlap scoring.
+    pub fn is_available(&self, worker_id: &str) -> bool {
+        self.is_ready(worker_id)
+            && self.load.get(worker_id).copied().unwrap_or(0.0) <= self.config.overload_threshold
+    }
+
+    /// Applies a worker's KV event to the router's view of what that
+    /// worker has cached.
+    pub fn ingest_event(&mut self, event: &KvCacheEvent) {
+        match event {
+            KvCacheEvent::Stored {
+                worker_id, blocks, ..
+            } => {
+                let hashes: Vec<u64> = blocks.iter().map(|b| b.block_hash).collect();
+                self.tree.insert(&hashes, worker_id);
+            }
+            KvCacheEvent::Removed {
+                worker_id,
+                block_hashes,
+                ..
+            } => {
+                self.tree.remove(block_hashes, worker_id);
+            }
+        }
+    }
+
+    /// Records a worker's most recently reported load (e.g. queue
+    /// depth or KV utilization; lower is better). Reports are taken
+    /// over the wire from the worker itself, so a non-finite value
+    /// (`NaN`/`inf`, from a buggy or malicious reporter) is dropped
+    /// rather than stored — otherwise it would poison comparisons in
+    /// [`Self::is_available`] and [`Self::scored_candidates`].
+    pub fn update_load(&mut self, worker_id: &str, load: f64) {
+        if !load.is_finite() {
+            return;
+        }
+        self.load.insert(worker_id.to_string(), load);
+    }
+
+    /// Scores every ready worker that has reported load or has at least
+    /// some overlap, and returns them sorted best-first.
+    fn scored_candidates(&self, token_block_hashes: &[u64]) -> Vec<(String, f64)> {
+        let matched = self.tree.matched_blocks(token_block_hashes);
+        let mut candidates: HashMap<String, f64> = self
+            .load
+            .keys()
+            .filter(|worker_id| self.is_ready(worker_id))
+            .map(|worker_id| (worker_id.clone(), 0.0))
+            .collect();
+        for worker_id in matched.keys() {
+            if self.is_ready(worker_id) {
+                candidates.entry(worker_id.clone()).or_insert(0.0);
+            }
+        }
+
+        let mut scored: Vec<(String, f64)> = candidates
+            .into_keys()
+            .map(|worker_id| {
+                let overlap = matched.get(&worker_id).copied().unwrap_or(0) as f64;
+                let load = self.load.get(&worker_id).copied().unwrap_or(0.0);
+                let score = self.config.overlap_weight * overlap - self.config.load_weight * load;
+                (worker_id, score)
+            })
+            .collect();
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        scored
+    }
+}
+
+impl PushRouter for KvAwareRouter {
+    fn select_worker(&mut self, token_block_hashes: &[u64]) -> Option<String> {
+        self.scored_candidates(token_block_hashes)
+            .into_iter()
+            .next()
+            .map(|(worker_id, _)| worker_id)
+    }
+
+    fn is_worker_available(&self, worker_id: &str) -> bool {
+        self.is_available(worker_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocker::kv_events::BlockInfo;
+
+    fn stored(worker_id: &str, hashes: &[u64]) -> KvCacheEvent {
+        KvCacheEvent::Stored {
+            worker_id: worker_id.to_string(),
+            event_id: 0,
+            blocks: hashes
+                .iter()
+                .map(|h| BlockInfo {
+                    block_hash: *h,
+                    parent_hash: None,
+                    num_tokens: 16,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn prefers_worker_with_more_overlap() {
+        let mut router = KvAwareRouter::new(KvRouterConfig::default());
+        router.ingest_event(&stored("worker-a", &[1, 2, 3]));
+        router.ingest_event(&stored("worker-b", &[1]));
+        router.update_load("worker-a", 0.0);
+        router.update_load("worker-b", 0.0);
+
+        assert_eq!(
+            router.select_worker(&[1, 2, 3]),
+            Some("worker-a".to_string())
+        );
+    }
+
+    #[test]
+    fn load_can_outweigh_a_small_overlap_advantage() {
+        let mut router = KvAwareRouter::new(KvRouterConfig {
+            overlap_weight: 1.0,
+            load_weight: 10.0,
+            ..KvRouterConfig::default()
+        });
+        router.ingest_event(&stored("worker-a", &[1]));
+        router.ingest_event(&stored("worker-b", &[1]));
+        router.update_load("worker-a", 5.0);
+        router.update_load("worker-b", 0.0);
+
+        assert_eq!(router.select_worker(&[1]), Some("worker-b".to_string()));
+    }
+
+    #[test]
+    fn no_known_workers_returns_none() {
+        let mut router = KvAwareRouter::new(KvRouterConfig::default());
+        assert_eq!(router.select_worker(&[1, 2]), None);
+    }
+
+    #[test]
+    fn not_ready_worker_is_excluded_even_with_best_overlap() {
+        let mut router = KvAwareRouter::new(KvRouterConfig::default());
+        router.ingest_event(&stored("worker-a", &[1, 2, 3]));
+        router.ingest_event(&stored("worker-b", &[1]));
+        router.update_load("worker-a", 0.0);
+        router.update_load("worker-b", 0.0);
+        router.update_readiness("worker-a", false);
+
+        assert_eq!(
+            router.select_worker(&[1, 2, 3]),
+            Some("worker-b".to_string())
+        );
+    }
+
+    #[test]
+    fn worker_that_becomes_ready_again_is_included() {
+        let mut router = KvAwareRouter::new(KvRouterConfig::default());
+        router.ingest_event(&stored("worker-a", &[1]));
+        router.update_load("worker-a", 0.0);
+        router.update_readiness("worker-a", false);
+        assert_eq!(router.select_worker(&[1]), None);
+
+        router.update_readiness("worker-a", true);
+        assert_eq!(router.select_worker(&[1]), Some("worker-a".to_string()));
+    }
+
+    #[test]
+    fn nan_load_report_is_ignored_instead_of_poisoning_selection() {
+        let mut router = KvAwareRouter::new(KvRouterConfig::default());
+        router.ingest_event(&stored("worker-a", &[1]));
+        router.ingest_event(&stored("worker-b", &[1]));
+        router.update_load("worker-a", f64::NAN);
+        router.update_load("worker-b", 5.0);
+
+        // The bogus report is dropped rather than stored, so worker-a
+        // keeps its prior (default) load and straightforwardly beats
+        // worker-b's real, higher load — and `select_worker` doesn't
+        // panic along the way.
+        assert_eq!(router.select_worker(&[1]), Some("worker-a".to_string()));
+    }
+}
@@ -0,0 +1,161 @@
+//! Session affinity on top of a [`PushRouter`]: requests carrying the
+//! same conversation/session ID prefer the worker that served previous
+//! turns, maximizing KV prefix reuse, while still falling back to
+//! ordinary selection when that worker is overloaded or gone.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use super::router::PushRouter;
+
+/// Wraps a [`PushRouter`] with per-session stickiness: the first
+/// request for a session picks a worker normally, and later requests
+/// for the same session reuse that worker as long as
+/// [`PushRouter::is_worker_available`] still considers it a good pick.
+/// Once it isn't, the session is silently rebalanced onto whatever the
+/// inner router picks next, and that becomes its new sticky worker.
+pub struct StickyRouter<R> {
+    inner: R,
+    sessions: Mutex<HashMap<String, String>>,
+}
+
+impl<R: PushRouter> StickyRouter<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            sessions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Picks a worker for `session_id`, preferring the worker that
+    /// served this session before when it's still available, and
+    /// otherwise falling back (and re-pinning the session) to the
+    /// inner router's normal selection.
+    pub fn select_worker_for_session(
+        &mut self,
+        session_id: &str,
+        token_block_hashes: &[u64],
+    ) -> Option<String> {
+        let pinned = self
+            .sessions
+            .lock()
+            .unwrap()
+            .get(session_id)
+            .filter(|worker_id| self.inner.is_worker_available(worker_id))
+            .cloned();
+        if let Some(worker_id) = pinned {
+            return Some(worker_id);
+        }
+
+        let worker_id = self.inner.select_worker(token_block_hashes)?;
+        self.sessions
+            .lock()
+            .unwrap()
+            .insert(session_id.to_string(), worker_id.clone());
+        Some(worker_id)
+    }
+
+    /// Drops a session's pinned worker, e.g. once its conversation has
+    /// ended, so the entry doesn't linger forever.
+    pub fn forget_session(&self, session_id: &str) {
+        self.sessions.lock().unwrap().remove(session_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kv_router::router::{KvAwareRouter, KvRouterConfig};
+    use crate::mocker::kv_events::{BlockInfo, KvCacheEvent};
+
+    fn stored(worker_id: &str, hashes: &[u64]) -> KvCacheEvent {
+        KvCacheEvent::Stored {
+            worker_id: worker_id.to_string(),
+            event_id: 0,
+            blocks: hashes
+                .iter()
+                .map(|h| BlockInfo {
+                    block_hash: *h,
+                    parent_hash: None,
+                    num_tokens: 16,
+                })
+                .collect(),
+        }
+    }
+
+    fn kv_router_with_threshold(overload_threshold: f64) -> KvAwareRouter {
+        KvAwareRouter::new(KvRouterConfig {
+            overload_threshold,
+            ..KvRouterConfig::default()
+        })
+    }
+
+    #[test]
+    fn repeated_session_sticks_to_the_same_worker() {
+        let mut kv_router = kv_router_with_threshold(10.0);
+        kv_router.ingest_event(&stored("worker-a", &[1, 2]));
+        kv_router.ingest_event(&stored("worker-b", &[1]));
+        kv_router.update_load("worker-a", 0.0);
+        kv_router.update_load("worker-b", 0.0);
+
+        let mut sticky = StickyRouter::new(kv_router);
+        let first = sticky
+            .select_worker_for_session("session-1", &[1, 2])
+            .unwrap();
+        let second = sticky.select_worker_for_session("session-1", &[]).unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn falls_back_and_rebalances_when_sticky_worker_is_overloaded() {
+        let mut kv_router = kv_router_with_threshold(10.0);
+        kv_router.ingest_event(&stored("worker-a", &[1]));
+        kv_router.ingest_event(&stored("worker-b", &[1]));
+        kv_router.update_load("worker-a", 0.0);
+        kv_router.update_load("worker-b", 0.0);
+
+        let mut sticky = StickyRouter::new(kv_router);
+        let first = sticky.select_worker_for_session("session-1", &[1]).unwrap();
+
+        sticky.inner.update_load(&first, 100.0);
+        let other = if first == "worker-a" {
+            "worker-b"
+        } else {
+            "worker-a"
+        };
+        let rebalanced = sticky.select_worker_for_session("session-1", &[1]).unwrap();
+        assert_eq!(rebalanced, other);
+
+        let subsequent = sticky.select_worker_for_session("session-1", &[1]).unwrap();
+        assert_eq!(subsequent, other);
+    }
+
+    #[test]
+    fn falls_back_when_sticky_worker_is_gone() {
+        let mut kv_router = kv_router_with_threshold(10.0);
+        kv_router.ingest_event(&stored("worker-a", &[1]));
+        kv_router.update_load("worker-a", 0.0);
+
+        let mut sticky = StickyRouter::new(kv_router);
+        let first = sticky.select_worker_for_session("session-1", &[1]).unwrap();
+        assert_eq!(first, "worker-a");
+
+        sticky.inner.update_readiness("worker-a", false);
+        assert_eq!(sticky.select_worker_for_session("session-1", &[1]), None);
+    }
+
+    #[test]
+    fn forgetting_a_session_clears_its_pinned_worker() {
+        let mut kv_router = kv_router_with_threshold(10.0);
+        kv_router.ingest_event(&stored("worker-a", &[1]));
+        kv_router.ingest_event(&stored("worker-b", &[1]));
+        kv_router.update_load("worker-a", 0.0);
+        kv_router.update_load("worker-b", 0.0);
+
+        let mut sticky = StickyRouter::new(kv_router);
+        sticky.select_worker_for_session("session-1", &[1]).unwrap();
+
+        sticky.forget_session("session-1");
+        assert!(!sticky.sessions.lock().unwrap().contains_key("session-1"));
+    }
+}
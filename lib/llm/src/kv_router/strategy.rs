@@ -0,0 +1,206 @@
+//! Pluggable instance-selection for the push router: the part of
+//! routing that only needs live load metrics, not KV-overlap awareness,
+//! selectable per endpoint at runtime instead of hard-coded into one
+//! policy. [`KvAwareRouter`](super::KvAwareRouter) covers the
+//! overlap-aware case; this is for everything else.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use rand::Rng;
+
+/// A worker candidate's current load, as reported by the worker itself
+/// (e.g. queue depth or in-flight request count).
+#[derive(Debug, Clone)]
+pub struct WorkerLoad {
+    pub worker_id: String,
+    pub outstanding_requests: u64,
+}
+
+/// Picks one worker out of a set of candidates. Implementations hold
+/// whatever state they need between calls (a round-robin cursor, an
+/// RNG) behind `&self` via interior mutability, so a strategy can be
+/// swapped out at runtime through [`StrategyRouter::set_strategy`]
+/// without callers needing `&mut`.
+pub trait LoadBalancingStrategy: Send + Sync {
+    fn select(&self, candidates: &[WorkerLoad]) -> Option<String>;
+}
+
+/// Cycles through candidates in the order given, ignoring load
+/// entirely.
+#[derive(Default)]
+pub struct RoundRobin {
+    next: AtomicUsize,
+}
+
+impl LoadBalancingStrategy for RoundRobin {
+    fn select(&self, candidates: &[WorkerLoad]) -> Option<String> {
+        if candidates.is_empty() {
+            return None;
+        }
+        let idx = self.next.fetch_add(1, Ordering::Relaxed) % candidates.len();
+        Some(candidates[idx].worker_id.clone())
+    }
+}
+
+/// Always picks the candidate with the fewest outstanding requests.
+/// Most accurate under light load; under heavy load every worker is
+/// checked on every request, which is the thing power-of-two avoids.
+#[derive(Default)]
+pub struct LeastOutstandingRequests;
+
+impl LoadBalancingStrategy for LeastOutstandingRequests {
+    fn select(&self, candidates: &[WorkerLoad]) -> Option<String> {
+        candidates
+            .iter()
+            .min_by_key(|c| c.outstanding_requests)
+            .map(|c| c.worker_id.clone())
+    }
+}
+
+/// Samples two distinct candidates at random and picks whichever has
+/// fewer outstanding requests: nearly as load-balanced as checking
+/// every candidate, for a fraction of the coordination cost.
+#[derive(Default)]
+pub struct PowerOfTwoChoices;
+
+impl LoadBalancingStrategy for PowerOfTwoChoices {
+    fn select(&self, candidates: &[WorkerLoad]) -> Option<String> {
+        match candidates.len() {
+            0 => None,
+            1 => Some(candidates[0].worker_id.clone()),
+            len => {
+                let mut rng = rand::thread_rng();
+                let i = rng.gen_range(0..len);
+                let mut j = rng.gen_range(0..len - 1);
+                if j >= i {
+                    j += 1;
+                }
+                let winner =
+                    if candidates[i].outstanding_requests <= candidates[j].outstanding_requests {
+                        &candidates[i]
+                    } else {
+                        &candidates[j]
+                    };
+                Some(winner.worker_id.clone())
+            }
+        }
+    }
+}
+
+/// Dispatches to a [`LoadBalancingStrategy`] per endpoint, falling back
+/// to a shared default for endpoints with no override, and letting the
+/// override be changed at runtime.
+pub struct StrategyRouter {
+    default_strategy: Arc<dyn LoadBalancingStrategy>,
+    overrides: Mutex<HashMap<String, Arc<dyn LoadBalancingStrategy>>>,
+}
+
+impl StrategyRouter {
+    pub fn new(default_strategy: Arc<dyn LoadBalancingStrategy>) -> Self {
+        Self {
+            default_strategy,
+            overrides: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Selects a strategy for `endpoint`, overriding whatever it used
+    /// before. Takes effect on the very next [`StrategyRouter::select`]
+    /// call for that endpoint.
+    pub fn set_strategy(&self, endpoint: &str, strategy: Arc<dyn LoadBalancingStrategy>) {
+        self.overrides
+            .lock()
+            .unwrap()
+            .insert(endpoint.to_string(), strategy);
+    }
+
+    pub fn select(&self, endpoint: &str, candidates: &[WorkerLoad]) -> Option<String> {
+        let strategy = self
+            .overrides
+            .lock()
+            .unwrap()
+            .get(endpoint)
+            .cloned()
+            .unwrap_or_else(|| self.default_strategy.clone());
+        strategy.select(candidates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn loads(pairs: &[(&str, u64)]) -> Vec<WorkerLoad> {
+        pairs
+            .iter()
+            .map(|(id, n)| WorkerLoad {
+                worker_id: id.to_string(),
+                outstanding_requests: *n,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn round_robin_cycles_through_candidates() {
+        let strategy = RoundRobin::default();
+        let candidates = loads(&[("a", 0), ("b", 0), ("c", 0)]);
+        let picks: Vec<String> = (0..4)
+            .map(|_| strategy.select(&candidates).unwrap())
+            .collect();
+        assert_eq!(picks, vec!["a", "b", "c", "a"]);
+    }
+
+    #[test]
+    fn round_robin_on_empty_candidates_returns_none() {
+        let strategy = RoundRobin::default();
+        assert_eq!(strategy.select(&[]), None);
+    }
+
+    #[test]
+    fn least_outstanding_picks_the_minimum() {
+        let strategy = LeastOutstandingRequests;
+        let candidates = loads(&[("a", 5), ("b", 1), ("c", 3)]);
+        assert_eq!(strategy.select(&candidates), Some("b".to_string()));
+    }
+
+    #[test]
+    fn power_of_two_always_returns_a_known_candidate() {
+        let strategy = PowerOfTwoChoices;
+        let candidates = loads(&[("a", 0), ("b", 0), ("c", 0), ("d", 0)]);
+        for _ in 0..50 {
+            let pick = strategy.select(&candidates).unwrap();
+            assert!(candidates.iter().any(|c| c.worker_id == pick));
+        }
+    }
+
+    #[test]
+    fn power_of_two_with_one_candidate_returns_it() {
+        let strategy = PowerOfTwoChoices;
+        let candidates = loads(&[("a", 0)]);
+        assert_eq!(strategy.select(&candidates), Some("a".to_string()));
+    }
+
+    #[test]
+    fn strategy_router_uses_default_until_overridden() {
+        let router = StrategyRouter::new(Arc::new(LeastOutstandingRequests));
+        let candidates = loads(&[("a", 5), ("b", 1)]);
+        assert_eq!(router.select("chat", &candidates), Some("b".to_string()));
+
+        router.set_strategy("chat", Arc::new(RoundRobin::default()));
+        assert_eq!(router.select("chat", &candidates), Some("a".to_string()));
+    }
+
+    #[test]
+    fn strategy_router_is_per_endpoint() {
+        let router = StrategyRouter::new(Arc::new(LeastOutstandingRequests));
+        router.set_strategy("embeddings", Arc::new(RoundRobin::default()));
+        let candidates = loads(&[("a", 5), ("b", 1)]);
+
+        assert_eq!(router.select("chat", &candidates), Some("b".to_string()));
+        assert_eq!(
+            router.select("embeddings", &candidates),
+            Some("a".to_string())
+        );
+    }
+}
@@ -0,0 +1,12 @@
+//! LLM-serving primitives shared across Dynamo components: KV-aware
+//! routing, frontend authentication, tenancy, audit logging, hot-reloadable
+//! config, and the
+//! `mocker` engine that simulates a serving fleet's scheduling behavior
+//! without any GPUs.
+
+pub mod audit;
+pub mod auth;
+pub mod config;
+pub mod kv_router;
+pub mod mocker;
+pub mod tenant;
@@ -0,0 +1,62 @@
+//! A simulated clock abstraction so the mocker can either run in real
+//! wall-clock time (useful for soak-testing a live mock server) or in
+//! accelerated virtual time (useful for replaying a 24-hour trace in
+//! minutes) while every other component's logic stays unchanged.
+
+use std::time::Duration;
+
+/// Advances simulated time, optionally sleeping in real time to match
+/// it.
+#[derive(Debug, Clone)]
+pub enum SimClock {
+    /// Actually sleeps for the requested duration, so elapsed time is
+    /// wall-clock time. Simulated time is not tracked in this mode:
+    /// `now_s` always returns `0.0`.
+    RealTime,
+    /// Never sleeps; `now_s` just accumulates instantly. Relative
+    /// timing between events (arrivals, prefill, decode) is preserved
+    /// for anything that reads `now_s`, even though no real time
+    /// passes.
+    Virtual { now_s: f64 },
+}
+
+impl SimClock {
+    pub fn real_time() -> Self {
+        SimClock::RealTime
+    }
+
+    pub fn virtual_time() -> Self {
+        SimClock::Virtual { now_s: 0.0 }
+    }
+
+    pub fn now_s(&self) -> f64 {
+        match self {
+            SimClock::RealTime => 0.0,
+            SimClock::Virtual { now_s } => *now_s,
+        }
+    }
+
+    /// Advances the clock by `duration`. Under `RealTime` this actually
+    /// sleeps; under `Virtual` it returns immediately after bumping the
+    /// internal counter.
+    pub async fn advance(&mut self, duration: Duration) {
+        match self {
+            SimClock::RealTime => tokio::time::sleep(duration).await,
+            SimClock::Virtual { now_s } => *now_s += duration.as_secs_f64(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn virtual_clock_advances_without_sleeping() {
+        let mut clock = SimClock::virtual_time();
+        let start = std::time::Instant::now();
+        clock.advance(Duration::from_secs(3600)).await;
+        assert!(start.elapsed() < Duration::from_millis(100));
+        assert_eq!(clock.now_s(), 3600.0);
+    }
+}
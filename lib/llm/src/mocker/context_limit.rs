@@ -0,0 +1,98 @@
+//! Context-length overflow handling: rejects or truncates requests that
+//! exceed a configurable max model length, emitting the same error
+//! shape the real engines produce, so frontend retry/error-handling
+//! paths can be exercised against simulated workers.
+
+use serde::Serialize;
+
+/// Matches the error body OpenAI-compatible engines return for a
+/// too-long prompt (`error.type == "invalid_request_error"`,
+/// `error.code == "context_length_exceeded"`).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ContextLengthError {
+    pub error: ContextLengthErrorBody,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ContextLengthErrorBody {
+    pub message: String,
+    #[serde(rename = "type")]
+    pub error_type: String,
+    pub code: String,
+}
+
+impl ContextLengthError {
+    pub fn new(requested_tokens: u32, max_model_len: u32) -> Self {
+        Self {
+            error: ContextLengthErrorBody {
+                message: format!(
+                    "This model's maximum context length is {max_model_len} tokens. \
+                     However, your messages resulted in {requested_tokens} tokens."
+                ),
+                error_type: "invalid_request_error".to_string(),
+                code: "context_length_exceeded".to_string(),
+            },
+        }
+    }
+}
+
+/// How a request exceeding `max_model_len` should be handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Reject the request outright with a [`ContextLengthError`].
+    Reject,
+    /// Truncate the prompt (dropping the earliest tokens) so it fits,
+    /// and serve it as if that had always been the input.
+    TruncateOldest,
+}
+
+/// Either an admitted token count (possibly truncated) or the rejection
+/// error to return to the caller.
+pub type ContextLimitOutcome = Result<u32, ContextLengthError>;
+
+/// Applies `policy` to a request with `prompt_tokens` against
+/// `max_model_len`, also accounting for `max_output_tokens` the way a
+/// real engine reserves output budget within the same context window.
+pub fn enforce_context_limit(
+    prompt_tokens: u32,
+    max_output_tokens: u32,
+    max_model_len: u32,
+    policy: OverflowPolicy,
+) -> ContextLimitOutcome {
+    let total = prompt_tokens + max_output_tokens;
+    if total <= max_model_len {
+        return Ok(prompt_tokens);
+    }
+    match policy {
+        OverflowPolicy::Reject => Err(ContextLengthError::new(total, max_model_len)),
+        OverflowPolicy::TruncateOldest => {
+            let truncated = max_model_len.saturating_sub(max_output_tokens);
+            Ok(truncated.min(prompt_tokens))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fits_within_limit_is_unaffected() {
+        let outcome = enforce_context_limit(100, 50, 4096, OverflowPolicy::Reject);
+        assert_eq!(outcome, Ok(100));
+    }
+
+    #[test]
+    fn reject_policy_returns_matching_error_shape() {
+        let outcome = enforce_context_limit(5000, 100, 4096, OverflowPolicy::Reject);
+        let err = outcome.unwrap_err();
+        assert_eq!(err.error.code, "context_length_exceeded");
+        assert_eq!(err.error.error_type, "invalid_request_error");
+    }
+
+    #[test]
+    fn truncate_policy_shrinks_to_fit() {
+        let outcome = enforce_context_limit(5000, 100, 4096, OverflowPolicy::TruncateOldest);
+        assert_eq!(outcome, Ok(3996));
+    }
+}
@@ -0,0 +1,90 @@
+//! Cost and energy modeling for simulated GPUs, so a simulation sweep
+//! can report cost-per-million-tokens and energy consumption alongside
+//! latency metrics for capacity planning, instead of latency numbers
+//! alone.
+
+use std::time::Duration;
+
+/// Pricing and power draw for one GPU type.
+#[derive(Debug, Clone)]
+pub struct GpuCostModel {
+    pub gpu_type: String,
+    pub dollars_per_hour: f64,
+    /// Watts drawn at 100% utilization; idle draw is modeled as a
+    /// fraction of this via `idle_watts_fraction`.
+    pub watts_at_full_utilization: f64,
+    pub idle_watts_fraction: f64,
+}
+
+impl GpuCostModel {
+    /// Rough defaults for a single high-end datacenter GPU; real sweeps
+    /// should plug in the actual SKU's numbers.
+    pub fn h100_sxm() -> Self {
+        Self {
+            gpu_type: "H100-SXM".to_string(),
+            dollars_per_hour: 4.0,
+            watts_at_full_utilization: 700.0,
+            idle_watts_fraction: 0.25,
+        }
+    }
+
+    pub fn watts_at(&self, utilization: f64) -> f64 {
+        let utilization = utilization.clamp(0.0, 1.0);
+        let idle = self.watts_at_full_utilization * self.idle_watts_fraction;
+        idle + (self.watts_at_full_utilization - idle) * utilization
+    }
+
+    pub fn dollar_cost(&self, wall_time: Duration) -> f64 {
+        self.dollars_per_hour * wall_time.as_secs_f64() / 3600.0
+    }
+
+    /// Energy in watt-hours for running at `utilization` for
+    /// `wall_time`.
+    pub fn energy_wh(&self, wall_time: Duration, utilization: f64) -> f64 {
+        self.watts_at(utilization) * wall_time.as_secs_f64() / 3600.0
+    }
+}
+
+/// Rolls up cost/energy across a run's GPUs into the headline numbers a
+/// capacity-planning report wants.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CostReport {
+    pub total_dollars: f64,
+    pub total_energy_wh: f64,
+    pub total_tokens: u64,
+}
+
+impl CostReport {
+    pub fn add_gpu_period(&mut self, model: &GpuCostModel, wall_time: Duration, utilization: f64, tokens: u64) {
+        self.total_dollars += model.dollar_cost(wall_time);
+        self.total_energy_wh += model.energy_wh(wall_time, utilization);
+        self.total_tokens += tokens;
+    }
+
+    pub fn dollars_per_million_tokens(&self) -> f64 {
+        if self.total_tokens == 0 {
+            0.0
+        } else {
+            self.total_dollars / self.total_tokens as f64 * 1_000_000.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn higher_utilization_draws_more_power() {
+        let model = GpuCostModel::h100_sxm();
+        assert!(model.watts_at(1.0) > model.watts_at(0.1));
+    }
+
+    #[test]
+    fn cost_per_million_tokens_scales_with_throughput() {
+        let model = GpuCostModel::h100_sxm();
+        let mut report = CostReport::default();
+        report.add_gpu_period(&model, Duration::from_secs(3600), 0.8, 1_000_000);
+        assert!((report.dollars_per_million_tokens() - model.dollars_per_hour).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,92 @@
+//! Two-role disaggregated topology: dedicated prefill instances hand off
+//! completed KV blocks to dedicated decode instances over a simulated
+//! transfer link, instead of one engine doing both phases in-place. Lets
+//! disaggregation trade-offs be evaluated without real prefill/decode
+//! workers.
+
+use std::time::Duration;
+
+use super::engine::MockRequest;
+use super::scheduler::Scheduler;
+
+/// Bandwidth/latency model for handing KV blocks from a prefill instance
+/// to a decode instance, e.g. over NVLink or RDMA in the real system.
+#[derive(Debug, Clone)]
+pub struct KvTransferLink {
+    pub fixed_latency_ms: f64,
+    pub bytes_per_token: u64,
+    pub bandwidth_gbps: f64,
+}
+
+impl KvTransferLink {
+    /// A same-rack NVLink-class default: low fixed latency, high
+    /// bandwidth.
+    pub fn fast_interconnect() -> Self {
+        Self {
+            fixed_latency_ms: 0.2,
+            bytes_per_token: 128 * 1024,
+            bandwidth_gbps: 400.0,
+        }
+    }
+
+    pub fn transfer_time(&self, tokens: u32) -> Duration {
+        let bytes = tokens as u64 * self.bytes_per_token;
+        let bandwidth_bytes_per_s = self.bandwidth_gbps * 1e9 / 8.0;
+        let transfer_s = bytes as f64 / bandwidth_bytes_per_s;
+        Duration::from_secs_f64(self.fixed_latency_ms / 1000.0 + transfer_s)
+    }
+}
+
+/// A prefill-tier and decode-tier pair, each with its own admission
+/// queue, connected by a [`KvTransferLink`]. Requests queue at the
+/// prefill tier, then queue again at the decode tier once their KV
+/// blocks have landed.
+pub struct DisaggregatedTopology {
+    pub prefill_queue: Scheduler,
+    pub decode_queue: Scheduler,
+    pub link: KvTransferLink,
+}
+
+impl DisaggregatedTopology {
+    pub fn new(link: KvTransferLink) -> Self {
+        Self {
+            prefill_queue: Scheduler::new(),
+            decode_queue: Scheduler::new(),
+            link,
+        }
+    }
+
+    /// Total time from prefill admission to decode admission for a
+    /// request of this size: the prefill instance's own latency plus the
+    /// KV handoff over `link`. Does not include either tier's queueing
+    /// delay, which depends on what else is in-flight.
+    pub fn handoff_latency(&self, request: &MockRequest, prefill_latency: Duration) -> Duration {
+        prefill_latency + self.link.transfer_time(request.new_tokens)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn larger_prompts_take_longer_to_transfer() {
+        let link = KvTransferLink::fast_interconnect();
+        let small = link.transfer_time(100);
+        let large = link.transfer_time(100_000);
+        assert!(large > small);
+    }
+
+    #[test]
+    fn handoff_includes_prefill_and_transfer() {
+        let topology = DisaggregatedTopology::new(KvTransferLink::fast_interconnect());
+        let request = MockRequest {
+            cached_tokens: 0,
+            new_tokens: 1000,
+            max_output_tokens: 50,
+        };
+        let prefill = Duration::from_millis(20);
+        let total = topology.handoff_latency(&request, prefill);
+        assert!(total > prefill);
+    }
+}
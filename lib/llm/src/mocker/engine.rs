@@ -0,0 +1,83 @@
+//! The mock engine itself: drives prefill/decode timing for simulated
+//! requests from a [`LatencyProfile`] instead of fixed constants.
+
+use std::time::Duration;
+
+use super::latency::LatencyProfile;
+use super::rng::SeededRng;
+
+/// A single simulated request's lifecycle knobs; the
+/// [`Scheduler`](super::scheduler::Scheduler) decides the order many of
+/// these are handed to the engine in.
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+    pub cached_tokens: u32,
+    pub new_tokens: u32,
+    pub max_output_tokens: u32,
+}
+
+/// Minimal single-request engine: computes the prefill latency and the
+/// per-step decode latency a request would see in isolation. Batching
+/// effects enter only through `decode_step_latency`'s `batch_size`
+/// argument.
+pub struct MockEngine {
+    profile: LatencyProfile,
+    /// Shared across every randomized decision this engine makes
+    /// (output-length sampling, fault injection, jitter), so a run is
+    /// fully reproducible from its seed plus its input trace.
+    rng: SeededRng,
+}
+
+impl MockEngine {
+    pub fn new(profile: LatencyProfile) -> Self {
+        Self::with_seed(profile, super::rng::DEFAULT_SEED)
+    }
+
+    pub fn with_seed(profile: LatencyProfile, seed: u64) -> Self {
+        Self {
+            profile,
+            rng: SeededRng::new(seed),
+        }
+    }
+
+    pub fn with_default_profile() -> Self {
+        Self::new(LatencyProfile::default())
+    }
+
+    /// Mutable access to the engine's RNG, for components (output-length
+    /// sampling, fault injection) that need to draw from the same
+    /// deterministic stream.
+    pub fn rng(&mut self) -> &mut SeededRng {
+        &mut self.rng
+    }
+
+    pub fn prefill_latency(&self, request: &MockRequest) -> Duration {
+        self.profile
+            .prefill_time(request.cached_tokens, request.new_tokens)
+    }
+
+    pub fn decode_step_latency(&self, batch_size: u32, active_kv_tokens: u64) -> Duration {
+        self.profile.decode_step_time(batch_size, active_kv_tokens)
+    }
+
+    /// Splits a request's prefill into `chunk_size`-token chunks instead
+    /// of processing it all at once, returning the latency of each
+    /// chunk in order. Tokens already processed by earlier chunks count
+    /// as cached context for later ones, matching how a real engine's
+    /// chunked prefill extends the KV cache incrementally. Interleaving
+    /// these chunks with decode steps for other in-flight requests is
+    /// the scheduler's job, not the engine's.
+    pub fn chunked_prefill_latency(&self, request: &MockRequest, chunk_size: u32) -> Vec<Duration> {
+        assert!(chunk_size > 0, "chunk_size must be positive");
+        let mut cached = request.cached_tokens;
+        let mut remaining = request.new_tokens;
+        let mut chunks = Vec::new();
+        while remaining > 0 {
+            let this_chunk = remaining.min(chunk_size);
+            chunks.push(self.profile.prefill_time(cached, this_chunk));
+            cached += this_chunk;
+            remaining -= this_chunk;
+        }
+        chunks
+    }
+}
@@ -0,0 +1,221 @@
+//! Pluggable cache-block eviction policies for the mocker's KV cache, so
+//! cache-policy research (which eviction strategy gives the best hit
+//! rate for a given workload) can be done entirely in simulation.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// A block eviction policy. Implementations decide which block to evict
+/// when the cache is full and a new block needs a slot.
+pub trait Evictor: std::fmt::Debug {
+    /// Records that `block` was just accessed (inserted or reused).
+    fn on_access(&mut self, block: u64);
+
+    /// Removes `block` from the policy's bookkeeping, e.g. because it
+    /// was explicitly freed rather than evicted.
+    fn on_remove(&mut self, block: u64);
+
+    /// Picks a block to evict from `candidates`, or `None` if empty.
+    fn evict(&mut self, candidates: &[u64]) -> Option<u64>;
+}
+
+/// Hit-rate bookkeeping shared across policies: call [`record_hit`] or
+/// [`record_miss`] on every lookup, independent of which `Evictor` is
+/// driving the cache.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HitRateStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl HitRateStats {
+    pub fn record_hit(&mut self) {
+        self.hits += 1;
+    }
+
+    pub fn record_miss(&mut self) {
+        self.misses += 1;
+    }
+
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// Least-recently-used: evicts the block with the oldest access.
+#[derive(Debug, Default)]
+pub struct LruEvictor {
+    last_access: HashMap<u64, u64>,
+    clock: u64,
+}
+
+impl Evictor for LruEvictor {
+    fn on_access(&mut self, block: u64) {
+        self.clock += 1;
+        self.last_access.insert(block, self.clock);
+    }
+
+    fn on_remove(&mut self, block: u64) {
+        self.last_access.remove(&block);
+    }
+
+    fn evict(&mut self, candidates: &[u64]) -> Option<u64> {
+        candidates
+            .iter()
+            .copied()
+            .min_by_key(|b| self.last_access.get(b).copied().unwrap_or(0))
+    }
+}
+
+/// Least-frequently-used: evicts the block with the fewest accesses.
+#[derive(Debug, Default)]
+pub struct LfuEvictor {
+    access_count: HashMap<u64, u64>,
+}
+
+impl Evictor for LfuEvictor {
+    fn on_access(&mut self, block: u64) {
+        *self.access_count.entry(block).or_insert(0) += 1;
+    }
+
+    fn on_remove(&mut self, block: u64) {
+        self.access_count.remove(&block);
+    }
+
+    fn evict(&mut self, candidates: &[u64]) -> Option<u64> {
+        candidates
+            .iter()
+            .copied()
+            .min_by_key(|b| self.access_count.get(b).copied().unwrap_or(0))
+    }
+}
+
+/// Simplified Adaptive Replacement Cache: tracks recency (like LRU) and
+/// frequency (like LFU) separately and evicts from whichever list is
+/// weighted more heavily. The weight is fixed: hits do not adjust it, so
+/// [`ArcEvictor::new`] (weight `0.5`) always evicts by recency and
+/// `ArcEvictor::default()` (weight `0.0`) always evicts by frequency. A
+/// full ARC also splits the cache into four ghost/real lists and adapts
+/// its weight; this mocker-scale version does neither.
+#[derive(Debug, Default)]
+pub struct ArcEvictor {
+    lru: LruEvictor,
+    lfu: LfuEvictor,
+    /// Weight toward recency in `[0.0, 1.0]`; `1.0` means pure LRU.
+    recency_weight: f64,
+}
+
+impl ArcEvictor {
+    pub fn new() -> Self {
+        Self {
+            lru: LruEvictor::default(),
+            lfu: LfuEvictor::default(),
+            recency_weight: 0.5,
+        }
+    }
+}
+
+impl Evictor for ArcEvictor {
+    fn on_access(&mut self, block: u64) {
+        self.lru.on_access(block);
+        self.lfu.on_access(block);
+    }
+
+    fn on_remove(&mut self, block: u64) {
+        self.lru.on_remove(block);
+        self.lfu.on_remove(block);
+    }
+
+    fn evict(&mut self, candidates: &[u64]) -> Option<u64> {
+        if candidates.len() <= 1 {
+            return candidates.first().copied();
+        }
+        if self.recency_weight >= 0.5 {
+            self.lru.evict(candidates)
+        } else {
+            self.lfu.evict(candidates)
+        }
+    }
+}
+
+/// Evicts any block whose time-to-live has expired, falling back to
+/// oldest-inserted among non-expired candidates if none have.
+#[derive(Debug)]
+pub struct TtlEvictor {
+    ttl: Duration,
+    inserted_at: HashMap<u64, Instant>,
+}
+
+impl TtlEvictor {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            inserted_at: HashMap::new(),
+        }
+    }
+}
+
+impl Evictor for TtlEvictor {
+    fn on_access(&mut self, block: u64) {
+        self.inserted_at.entry(block).or_insert_with(Instant::now);
+    }
+
+    fn on_remove(&mut self, block: u64) {
+        self.inserted_at.remove(&block);
+    }
+
+    fn evict(&mut self, candidates: &[u64]) -> Option<u64> {
+        let now = Instant::now();
+        candidates
+            .iter()
+            .copied()
+            .find(|b| {
+                self.inserted_at
+                    .get(b)
+                    .is_some_and(|t| now.duration_since(*t) >= self.ttl)
+            })
+            .or_else(|| {
+                candidates
+                    .iter()
+                    .copied()
+                    .min_by_key(|b| self.inserted_at.get(b).copied())
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lru_evicts_oldest_accessed() {
+        let mut evictor = LruEvictor::default();
+        evictor.on_access(1);
+        evictor.on_access(2);
+        evictor.on_access(1);
+        assert_eq!(evictor.evict(&[1, 2]), Some(2));
+    }
+
+    #[test]
+    fn lfu_evicts_least_frequently_accessed() {
+        let mut evictor = LfuEvictor::default();
+        evictor.on_access(1);
+        evictor.on_access(1);
+        evictor.on_access(2);
+        assert_eq!(evictor.evict(&[1, 2]), Some(2));
+    }
+
+    #[test]
+    fn hit_rate_stats_compute_fraction() {
+        let mut stats = HitRateStats::default();
+        stats.record_hit();
+        stats.record_hit();
+        stats.record_miss();
+        assert!((stats.hit_rate() - (2.0 / 3.0)).abs() < 1e-9);
+    }
+}
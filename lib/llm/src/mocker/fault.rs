@@ -0,0 +1,161 @@
+//! Fault injection for simulated workers, so the router's failure
+//! handling and the planner's reaction to a degraded fleet can be
+//! exercised in CI-style simulations instead of only against real
+//! hardware failures.
+
+use std::time::Duration;
+
+use super::rng::SeededRng;
+
+/// A single fault to apply, scheduled at a simulated wall-clock time.
+#[derive(Debug, Clone)]
+pub enum Fault {
+    /// The worker crashes and is unavailable until `restart_after`
+    /// elapses, at which point it comes back with a cold cache.
+    CrashRestart { restart_after: Duration },
+    /// Every request's latency is multiplied by `factor` until the
+    /// fault's `duration` elapses.
+    TransientSlowdown { factor: f64, duration: Duration },
+    /// The response for an in-flight request is dropped rather than
+    /// delivered, as if the connection died mid-stream.
+    DroppedResponse,
+    /// KV cache events (block stored/freed) are published `delay` later
+    /// than they actually happened, so router prefix-matching sees
+    /// stale state.
+    DelayedKvEvents { delay: Duration },
+}
+
+/// A scheduled fault: `at_s` is the simulated time (seconds since sim
+/// start) it takes effect.
+#[derive(Debug, Clone)]
+pub struct ScheduledFault {
+    pub at_s: f64,
+    pub fault: Fault,
+}
+
+/// Drives fault injection for one simulated worker: holds the schedule
+/// and tracks which fault (if any) is currently active.
+#[derive(Debug, Default)]
+pub struct FaultInjector {
+    schedule: Vec<ScheduledFault>,
+    active: Option<Fault>,
+    active_until_s: f64,
+}
+
+impl FaultInjector {
+    pub fn new(schedule: Vec<ScheduledFault>) -> Self {
+        Self {
+            schedule,
+            active: None,
+            active_until_s: 0.0,
+        }
+    }
+
+    /// Generates a schedule of random transient slowdowns and dropped
+    /// responses over `[0, horizon_s)`, for sweeps that want "some
+    /// baseline flakiness" without hand-authoring a trace.
+    pub fn random(horizon_s: f64, fault_rate_per_s: f64, rng: &mut SeededRng) -> Self {
+        let mut schedule = Vec::new();
+        let mut t = 0.0;
+        while t < horizon_s {
+            let gap = -(1.0 - rng.gen_range(0.0..1.0)).ln() / fault_rate_per_s.max(1e-9);
+            t += gap;
+            if t >= horizon_s {
+                break;
+            }
+            let fault = if rng.gen_bool(0.5) {
+                Fault::TransientSlowdown {
+                    factor: 2.0 + rng.gen_range(0.0..3.0),
+                    duration: Duration::from_secs_f64(rng.gen_range(1.0..10.0)),
+                }
+            } else {
+                Fault::DroppedResponse
+            };
+            schedule.push(ScheduledFault { at_s: t, fault });
+        }
+        Self::new(schedule)
+    }
+
+    /// Advances the injector to simulated time `now_s`, activating any
+    /// fault whose time has arrived and clearing any that has expired.
+    pub fn advance(&mut self, now_s: f64) {
+        if self.active.is_some() && now_s >= self.active_until_s {
+            self.active = None;
+        }
+        while let Some(next) = self.schedule.first() {
+            if next.at_s > now_s {
+                break;
+            }
+            let scheduled = self.schedule.remove(0);
+            self.active_until_s = now_s
+                + match &scheduled.fault {
+                    Fault::CrashRestart { restart_after } => restart_after.as_secs_f64(),
+                    Fault::TransientSlowdown { duration, .. } => duration.as_secs_f64(),
+                    Fault::DroppedResponse | Fault::DelayedKvEvents { .. } => 0.0,
+                };
+            self.active = Some(scheduled.fault);
+        }
+    }
+
+    pub fn is_crashed(&self) -> bool {
+        matches!(self.active, Some(Fault::CrashRestart { .. }))
+    }
+
+    /// Multiplier to apply to normal latency given the currently active
+    /// fault; `1.0` if none.
+    pub fn latency_multiplier(&self) -> f64 {
+        match &self.active {
+            Some(Fault::TransientSlowdown { factor, .. }) => *factor,
+            _ => 1.0,
+        }
+    }
+
+    pub fn should_drop_response(&self) -> bool {
+        matches!(self.active, Some(Fault::DroppedResponse))
+    }
+
+    pub fn kv_event_delay(&self) -> Duration {
+        match &self.active {
+            Some(Fault::DelayedKvEvents { delay }) => *delay,
+            _ => Duration::ZERO,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slowdown_applies_multiplier_while_active() {
+        let mut injector = FaultInjector::new(vec![ScheduledFault {
+            at_s: 1.0,
+            fault: Fault::TransientSlowdown {
+                factor: 3.0,
+                duration: Duration::from_secs(2),
+            },
+        }]);
+        injector.advance(0.5);
+        assert_eq!(injector.latency_multiplier(), 1.0);
+
+        injector.advance(1.0);
+        assert_eq!(injector.latency_multiplier(), 3.0);
+
+        injector.advance(3.5);
+        assert_eq!(injector.latency_multiplier(), 1.0);
+    }
+
+    #[test]
+    fn crash_marks_worker_unavailable_until_restart() {
+        let mut injector = FaultInjector::new(vec![ScheduledFault {
+            at_s: 0.0,
+            fault: Fault::CrashRestart {
+                restart_after: Duration::from_secs(5),
+            },
+        }]);
+        injector.advance(0.0);
+        assert!(injector.is_crashed());
+        injector.advance(6.0);
+        assert!(!injector.is_crashed());
+    }
+}
@@ -0,0 +1,128 @@
+//! KV cache events in the same wire format the real block manager
+//! publishes, so KV-aware routers and indexers can be tested against
+//! simulated workers with no code changes.
+
+use serde::{Deserialize, Serialize};
+
+use super::prefix_cache::BLOCK_SIZE;
+
+/// One block's hash plus the metadata a router needs to match prefixes
+/// without re-tokenizing: its position in the sequence (via
+/// `parent_hash`) and how many tokens it covers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockInfo {
+    pub block_hash: u64,
+    pub parent_hash: Option<u64>,
+    pub num_tokens: u32,
+}
+
+/// A batch of blocks becoming resident on a worker, or being freed from
+/// it. Matches the real block manager's event shape: one worker id, one
+/// monotonic event id, and a list of block hashes affected together
+/// (blocks from the same request are usually stored/removed as a unit).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event_type", rename_all = "snake_case")]
+pub enum KvCacheEvent {
+    Stored {
+        worker_id: String,
+        event_id: u64,
+        blocks: Vec<BlockInfo>,
+    },
+    Removed {
+        worker_id: String,
+        event_id: u64,
+        block_hashes: Vec<u64>,
+    },
+}
+
+/// Wraps a worker's KV state with event emission: every block
+/// store/remove also appends the matching [`KvCacheEvent`] so callers
+/// can drain and publish them to the real event plane's transport.
+#[derive(Debug)]
+pub struct KvEventEmitter {
+    worker_id: String,
+    next_event_id: u64,
+    pending: Vec<KvCacheEvent>,
+}
+
+impl KvEventEmitter {
+    pub fn new(worker_id: impl Into<String>) -> Self {
+        Self {
+            worker_id: worker_id.into(),
+            next_event_id: 0,
+            pending: Vec::new(),
+        }
+    }
+
+    fn next_id(&mut self) -> u64 {
+        let id = self.next_event_id;
+        self.next_event_id += 1;
+        id
+    }
+
+    /// Records that `hashes` (in order, chained via `parent_hash` the
+    /// same way [`super::prefix_cache::hash_blocks`] chains them) just
+    /// became resident.
+    pub fn emit_stored(&mut self, hashes: &[u64]) {
+        let blocks = hashes
+            .iter()
+            .enumerate()
+            .map(|(idx, hash)| BlockInfo {
+                block_hash: *hash,
+                parent_hash: if idx == 0 { None } else { Some(hashes[idx - 1]) },
+                num_tokens: BLOCK_SIZE as u32,
+            })
+            .collect();
+        let event_id = self.next_id();
+        self.pending.push(KvCacheEvent::Stored {
+            worker_id: self.worker_id.clone(),
+            event_id,
+            blocks,
+        });
+    }
+
+    pub fn emit_removed(&mut self, hashes: Vec<u64>) {
+        let event_id = self.next_id();
+        self.pending.push(KvCacheEvent::Removed {
+            worker_id: self.worker_id.clone(),
+            event_id,
+            block_hashes: hashes,
+        });
+    }
+
+    /// Drains all events recorded since the last drain, in emission
+    /// order, for the caller to publish to the real event-plane
+    /// transport.
+    pub fn drain(&mut self) -> Vec<KvCacheEvent> {
+        std::mem::take(&mut self.pending)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stored_event_chains_parent_hashes() {
+        let mut emitter = KvEventEmitter::new("worker-0");
+        emitter.emit_stored(&[1, 2, 3]);
+        let events = emitter.drain();
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            KvCacheEvent::Stored { blocks, .. } => {
+                assert_eq!(blocks[0].parent_hash, None);
+                assert_eq!(blocks[1].parent_hash, Some(1));
+                assert_eq!(blocks[2].parent_hash, Some(2));
+            }
+            _ => panic!("expected Stored event"),
+        }
+    }
+
+    #[test]
+    fn drain_clears_pending_events() {
+        let mut emitter = KvEventEmitter::new("worker-0");
+        emitter.emit_removed(vec![1, 2]);
+        assert_eq!(emitter.drain().len(), 1);
+        assert_eq!(emitter.drain().len(), 0);
+    }
+}
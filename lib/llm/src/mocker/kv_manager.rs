@@ -0,0 +1,114 @@
+//! Models what happens to a preempted decode's KV blocks: either thrown
+//! away and recomputed from scratch on resume (`Recompute`), or moved to
+//! a simulated host-memory tier and swapped back in later (`Swap`).
+//! Tracks wasted work per policy so the trade-off can be measured
+//! instead of assumed.
+
+use std::time::Duration;
+
+use super::scheduler::ActiveDecode;
+
+/// How a preempted decode's KV blocks are handled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PreemptionPolicy {
+    /// Drop the KV blocks; the request re-prefills everything from
+    /// scratch when it's re-admitted. Simple, but wastes all the compute
+    /// already spent on the evicted tokens.
+    Recompute,
+    /// Copy the KV blocks out to a simulated host tier at `bandwidth_gbps`
+    /// and copy them back in when re-admitted, at the cost of transfer
+    /// latency in both directions.
+    Swap { bandwidth_gbps: f64 },
+}
+
+/// Running per-policy statistics across a simulation, so sweeps can
+/// compare recompute and swap under the same trace.
+#[derive(Debug, Default, Clone)]
+pub struct PreemptionStats {
+    pub victims: u64,
+    pub recomputed_tokens: u64,
+    pub swapped_tokens: u64,
+    pub total_swap_time: Duration,
+}
+
+/// Manages the fate of preempted decodes under a fixed policy, recording
+/// the work wasted (or transfer cost incurred) along the way.
+#[derive(Debug)]
+pub struct KvManager {
+    policy: PreemptionPolicy,
+    stats: PreemptionStats,
+}
+
+impl KvManager {
+    pub fn new(policy: PreemptionPolicy) -> Self {
+        Self {
+            policy,
+            stats: PreemptionStats::default(),
+        }
+    }
+
+    pub fn stats(&self) -> &PreemptionStats {
+        &self.stats
+    }
+
+    /// Handles one victim's preemption, returning the extra delay it
+    /// will see before it can resume (full re-prefill time for
+    /// `Recompute`, swap-in transfer time for `Swap`; swap-out happens
+    /// in the background and isn't on the victim's own critical path).
+    pub fn preempt(&mut self, victim: &ActiveDecode, prefill_time_for_tokens: Duration) -> Duration {
+        self.stats.victims += 1;
+        match self.policy {
+            PreemptionPolicy::Recompute => {
+                self.stats.recomputed_tokens += victim.active_kv_tokens;
+                prefill_time_for_tokens
+            }
+            PreemptionPolicy::Swap { bandwidth_gbps } => {
+                self.stats.swapped_tokens += victim.active_kv_tokens;
+                let swap_in = swap_transfer_time(victim.active_kv_tokens, bandwidth_gbps);
+                self.stats.total_swap_time += swap_in * 2;
+                swap_in
+            }
+        }
+    }
+}
+
+fn swap_transfer_time(tokens: u64, bandwidth_gbps: f64) -> Duration {
+    const BYTES_PER_TOKEN: u64 = 128 * 1024;
+    let bytes = tokens * BYTES_PER_TOKEN;
+    let bandwidth_bytes_per_s = bandwidth_gbps * 1e9 / 8.0;
+    Duration::from_secs_f64(bytes as f64 / bandwidth_bytes_per_s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocker::scheduler::Priority;
+
+    fn victim(tokens: u64) -> ActiveDecode {
+        ActiveDecode {
+            request_id: 1,
+            priority: Priority::Batch,
+            active_kv_tokens: tokens,
+        }
+    }
+
+    #[test]
+    fn recompute_tracks_wasted_tokens_not_transfer_time() {
+        let mut manager = KvManager::new(PreemptionPolicy::Recompute);
+        let delay = manager.preempt(&victim(1000), Duration::from_millis(50));
+        assert_eq!(delay, Duration::from_millis(50));
+        assert_eq!(manager.stats().recomputed_tokens, 1000);
+        assert_eq!(manager.stats().total_swap_time, Duration::ZERO);
+    }
+
+    #[test]
+    fn swap_tracks_transfer_time_not_recompute() {
+        let mut manager = KvManager::new(PreemptionPolicy::Swap {
+            bandwidth_gbps: 200.0,
+        });
+        manager.preempt(&victim(1_000_000), Duration::from_secs(1));
+        assert_eq!(manager.stats().swapped_tokens, 1_000_000);
+        assert!(manager.stats().total_swap_time > Duration::ZERO);
+        assert_eq!(manager.stats().recomputed_tokens, 0);
+    }
+}
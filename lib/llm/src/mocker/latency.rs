@@ -0,0 +1,85 @@
+//! Profile-driven latency model for the mocker, replacing fixed timing
+//! constants with curves fit to real engine benchmarks.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// A latency profile loaded from a benchmark sweep: prefill time as a
+/// function of `(cached_tokens, new_tokens)`, and decode step time as a
+/// function of `(batch_size, active_kv_tokens)`. Both are modeled as an
+/// affine fit (`base + per_token_coeff * x + per_unit_coeff * y`) since
+/// that's what real prefill/decode sweeps look like to first order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyProfile {
+    pub prefill_base_ms: f64,
+    pub prefill_per_new_token_ms: f64,
+    pub prefill_cache_hit_discount_ms: f64,
+    pub decode_base_ms: f64,
+    pub decode_per_batch_slot_ms: f64,
+    pub decode_per_active_kv_token_ms: f64,
+}
+
+impl Default for LatencyProfile {
+    /// Rough defaults in the right ballpark for a mid-size dense model on
+    /// a single modern GPU; real simulations should load a profile fit
+    /// from benchmark output with [`LatencyProfile::from_json_file`]
+    /// instead.
+    fn default() -> Self {
+        Self {
+            prefill_base_ms: 5.0,
+            prefill_per_new_token_ms: 0.15,
+            prefill_cache_hit_discount_ms: 0.05,
+            decode_base_ms: 8.0,
+            decode_per_batch_slot_ms: 0.6,
+            decode_per_active_kv_token_ms: 0.0005,
+        }
+    }
+}
+
+impl LatencyProfile {
+    pub fn from_json_file(path: &std::path::Path) -> std::io::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        serde_json::from_str(&contents)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    /// Time to prefill `new_tokens` new tokens given `cached_tokens`
+    /// already present in the KV cache from a prefix match.
+    pub fn prefill_time(&self, cached_tokens: u32, new_tokens: u32) -> Duration {
+        let discount = self.prefill_cache_hit_discount_ms * cached_tokens as f64;
+        let raw = self.prefill_base_ms + self.prefill_per_new_token_ms * new_tokens as f64
+            - discount;
+        Duration::from_secs_f64(raw.max(0.1) / 1000.0)
+    }
+
+    /// Time for one decode step given the current batch size and total
+    /// active KV tokens across that batch.
+    pub fn decode_step_time(&self, batch_size: u32, active_kv_tokens: u64) -> Duration {
+        let raw = self.decode_base_ms
+            + self.decode_per_batch_slot_ms * batch_size as f64
+            + self.decode_per_active_kv_token_ms * active_kv_tokens as f64;
+        Duration::from_secs_f64(raw.max(0.1) / 1000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_hits_reduce_prefill_time() {
+        let profile = LatencyProfile::default();
+        let cold = profile.prefill_time(0, 1000);
+        let warm = profile.prefill_time(900, 1000);
+        assert!(warm < cold);
+    }
+
+    #[test]
+    fn larger_batches_cost_more_decode_time() {
+        let profile = LatencyProfile::default();
+        let small = profile.decode_step_time(1, 1000);
+        let large = profile.decode_step_time(64, 1000);
+        assert!(large > small);
+    }
+}
@@ -0,0 +1,147 @@
+//! Metrics export for the mocker: the same measurements the real workers
+//! emit (queue depth, KV utilization, TTFT/ITL, batch size), exported
+//! under a `dynamo_mocker_*` prefix. The real workers' own metric names
+//! are not used, so a simulated fleet shows up under different series
+//! than a real one.
+
+use std::fmt::Write as _;
+
+/// A single-sample histogram recorder: keeps every observation so the
+/// end-of-run summary can report exact percentiles. Fine at mocker
+/// scale; a real exporter would use fixed buckets instead.
+#[derive(Debug, Default, Clone)]
+pub struct Histogram {
+    samples: Vec<f64>,
+}
+
+impl Histogram {
+    pub fn observe(&mut self, value: f64) {
+        self.samples.push(value);
+    }
+
+    pub fn count(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn mean(&self) -> f64 {
+        if self.samples.is_empty() {
+            0.0
+        } else {
+            self.samples.iter().sum::<f64>() / self.samples.len() as f64
+        }
+    }
+
+    /// `p` in `[0.0, 1.0]`.
+    pub fn percentile(&self, p: f64) -> f64 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        let mut sorted = self.samples.clone();
+        sorted.sort_by(f64::total_cmp);
+        let idx = ((sorted.len() - 1) as f64 * p).round() as usize;
+        sorted[idx]
+    }
+}
+
+/// The gauges and latency distributions a real Dynamo worker reports,
+/// exported under `dynamo_mocker_*` names (see [`Self::to_prometheus`]).
+#[derive(Debug, Default)]
+pub struct MockerMetrics {
+    pub queue_depth: u64,
+    pub kv_utilization: f64,
+    pub batch_size: u64,
+    pub ttft_ms: Histogram,
+    pub itl_ms: Histogram,
+}
+
+impl MockerMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Renders the gauges/histograms in Prometheus text exposition
+    /// format, under the `dynamo_mocker_*` namespace.
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# TYPE dynamo_mocker_queue_depth gauge");
+        let _ = writeln!(out, "dynamo_mocker_queue_depth {}", self.queue_depth);
+        let _ = writeln!(out, "# TYPE dynamo_mocker_kv_utilization gauge");
+        let _ = writeln!(
+            out,
+            "dynamo_mocker_kv_utilization {}",
+            self.kv_utilization
+        );
+        let _ = writeln!(out, "# TYPE dynamo_mocker_batch_size gauge");
+        let _ = writeln!(out, "dynamo_mocker_batch_size {}", self.batch_size);
+        let _ = writeln!(out, "# TYPE dynamo_mocker_ttft_ms summary");
+        let _ = writeln!(
+            out,
+            "dynamo_mocker_ttft_ms{{quantile=\"0.5\"}} {}",
+            self.ttft_ms.percentile(0.5)
+        );
+        let _ = writeln!(
+            out,
+            "dynamo_mocker_ttft_ms{{quantile=\"0.99\"}} {}",
+            self.ttft_ms.percentile(0.99)
+        );
+        let _ = writeln!(out, "# TYPE dynamo_mocker_itl_ms summary");
+        let _ = writeln!(
+            out,
+            "dynamo_mocker_itl_ms{{quantile=\"0.5\"}} {}",
+            self.itl_ms.percentile(0.5)
+        );
+        let _ = writeln!(
+            out,
+            "dynamo_mocker_itl_ms{{quantile=\"0.99\"}} {}",
+            self.itl_ms.percentile(0.99)
+        );
+        out
+    }
+
+    /// An end-of-run summary suitable for archiving alongside a
+    /// simulation sweep's other output files.
+    pub fn to_json_summary(&self) -> serde_json::Value {
+        serde_json::json!({
+            "queue_depth": self.queue_depth,
+            "kv_utilization": self.kv_utilization,
+            "batch_size": self.batch_size,
+            "ttft_ms": {
+                "count": self.ttft_ms.count(),
+                "mean": self.ttft_ms.mean(),
+                "p50": self.ttft_ms.percentile(0.5),
+                "p99": self.ttft_ms.percentile(0.99),
+            },
+            "itl_ms": {
+                "count": self.itl_ms.count(),
+                "mean": self.itl_ms.mean(),
+                "p50": self.itl_ms.percentile(0.5),
+                "p99": self.itl_ms.percentile(0.99),
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentile_matches_sorted_position() {
+        let mut hist = Histogram::default();
+        for v in [10.0, 30.0, 20.0, 40.0] {
+            hist.observe(v);
+        }
+        assert_eq!(hist.percentile(0.0), 10.0);
+        assert_eq!(hist.percentile(1.0), 40.0);
+    }
+
+    #[test]
+    fn prometheus_output_includes_metric_names() {
+        let mut metrics = MockerMetrics::new();
+        metrics.queue_depth = 5;
+        metrics.ttft_ms.observe(12.0);
+        let text = metrics.to_prometheus();
+        assert!(text.contains("dynamo_mocker_queue_depth 5"));
+        assert!(text.contains("dynamo_mocker_ttft_ms"));
+    }
+}
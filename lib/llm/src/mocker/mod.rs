@@ -0,0 +1,32 @@
+//! A GPU-free simulation of a Dynamo LLM worker: the same scheduling and
+//! KV-cache shape as a real vLLM/TRT-LLM engine, but with token generation
+//! replaced by a latency model, so routers, planners, and schedulers can
+//! be exercised against realistic timing without hardware.
+
+pub mod autoscale;
+pub mod block_manager_integration;
+pub mod clock;
+pub mod context_limit;
+pub mod cost;
+pub mod disagg;
+pub mod engine;
+pub mod evictor;
+pub mod fault;
+pub mod kv_events;
+pub mod kv_manager;
+pub mod latency;
+pub mod lora;
+pub mod metrics;
+pub mod multi_model;
+pub mod multi_worker_cache;
+pub mod prefix_cache;
+pub mod replay;
+pub mod rng;
+pub mod scheduler;
+pub mod sequence;
+pub mod server;
+pub mod sla;
+pub mod tensor_parallel;
+pub mod timeline;
+pub mod trace;
+pub mod warmup;
@@ -0,0 +1,115 @@
+//! Multiple models co-located on one simulated GPU, each with its own
+//! latency profile and a share of total KV memory, so co-location
+//! trade-offs (static vs dynamic partitioning) can be studied before
+//! enabling them in production.
+
+use std::collections::HashMap;
+
+use super::latency::LatencyProfile;
+
+/// How the GPU's KV memory is divided across co-located models.
+#[derive(Debug, Clone)]
+pub enum MemoryPartitioning {
+    /// Each model gets a fixed share of `total_kv_tokens`, specified as
+    /// a fraction; unused capacity in one model's partition can't be
+    /// borrowed by another.
+    Static { shares: HashMap<String, f64> },
+    /// All models draw from one shared pool up to `total_kv_tokens`;
+    /// whichever models are busiest get more of it, at the cost of one
+    /// model being able to starve another under skewed load.
+    Dynamic,
+}
+
+/// One GPU hosting several models, each with its own timing and a slice
+/// of the shared KV budget.
+pub struct MultiModelGpu {
+    pub total_kv_tokens: u64,
+    pub partitioning: MemoryPartitioning,
+    profiles: HashMap<String, LatencyProfile>,
+    active_kv_tokens: HashMap<String, u64>,
+}
+
+impl MultiModelGpu {
+    pub fn new(total_kv_tokens: u64, partitioning: MemoryPartitioning) -> Self {
+        Self {
+            total_kv_tokens,
+            partitioning,
+            profiles: HashMap::new(),
+            active_kv_tokens: HashMap::new(),
+        }
+    }
+
+    pub fn register_model(&mut self, name: &str, profile: LatencyProfile) {
+        self.profiles.insert(name.to_string(), profile);
+        self.active_kv_tokens.entry(name.to_string()).or_insert(0);
+    }
+
+    pub fn profile(&self, name: &str) -> Option<&LatencyProfile> {
+        self.profiles.get(name)
+    }
+
+    /// KV tokens `name` may currently use without exceeding its
+    /// partition (static) or the GPU's remaining headroom (dynamic).
+    pub fn available_kv_tokens(&self, name: &str) -> u64 {
+        match &self.partitioning {
+            MemoryPartitioning::Static { shares } => {
+                let share = shares.get(name).copied().unwrap_or(0.0);
+                let capacity = (self.total_kv_tokens as f64 * share) as u64;
+                let used = self.active_kv_tokens.get(name).copied().unwrap_or(0);
+                capacity.saturating_sub(used)
+            }
+            MemoryPartitioning::Dynamic => {
+                let used: u64 = self.active_kv_tokens.values().sum();
+                self.total_kv_tokens.saturating_sub(used)
+            }
+        }
+    }
+
+    /// Reserves `tokens` of KV capacity for `name`, returning `false`
+    /// (and reserving nothing) if that would exceed what's available to
+    /// it.
+    pub fn reserve(&mut self, name: &str, tokens: u64) -> bool {
+        if tokens > self.available_kv_tokens(name) {
+            return false;
+        }
+        *self.active_kv_tokens.entry(name.to_string()).or_insert(0) += tokens;
+        true
+    }
+
+    pub fn release(&mut self, name: &str, tokens: u64) {
+        if let Some(used) = self.active_kv_tokens.get_mut(name) {
+            *used = used.saturating_sub(tokens);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn static_partition_caps_each_model_independently() {
+        let mut shares = HashMap::new();
+        shares.insert("a".to_string(), 0.5);
+        shares.insert("b".to_string(), 0.5);
+        let mut gpu = MultiModelGpu::new(1000, MemoryPartitioning::Static { shares });
+        gpu.register_model("a", LatencyProfile::default());
+        gpu.register_model("b", LatencyProfile::default());
+
+        assert!(gpu.reserve("a", 500));
+        assert!(!gpu.reserve("a", 1));
+        assert!(gpu.reserve("b", 500));
+    }
+
+    #[test]
+    fn dynamic_partition_lets_one_model_use_the_whole_pool() {
+        let mut gpu = MultiModelGpu::new(1000, MemoryPartitioning::Dynamic);
+        gpu.register_model("a", LatencyProfile::default());
+        gpu.register_model("b", LatencyProfile::default());
+
+        assert!(gpu.reserve("a", 1000));
+        assert!(!gpu.reserve("b", 1));
+        gpu.release("a", 500);
+        assert!(gpu.reserve("b", 500));
+    }
+}
@@ -0,0 +1,111 @@
+//! Runs N independent [`PrefixCache`]s behind one interface, one per
+//! simulated worker, so KV-aware routing algorithms can be evaluated on
+//! aggregate hit rate and load balance without any real hardware behind
+//! them.
+
+use super::prefix_cache::{PrefixCache, PrefixCacheStats};
+
+/// A fleet of mock workers, each with its own prefix cache. Routing
+/// algorithms under test score workers with [`Self::matched_blocks`]
+/// and [`Self::load`], then commit a request with [`Self::route`].
+pub struct WorkerFleet {
+    caches: Vec<PrefixCache>,
+    load: Vec<u64>,
+}
+
+impl WorkerFleet {
+    pub fn new(num_workers: usize) -> Self {
+        Self {
+            caches: (0..num_workers).map(|_| PrefixCache::new()).collect(),
+            load: vec![0; num_workers],
+        }
+    }
+
+    pub fn num_workers(&self) -> usize {
+        self.caches.len()
+    }
+
+    pub fn load(&self, worker: usize) -> u64 {
+        self.load[worker]
+    }
+
+    /// Per-worker cached-block overlap for `tokens`, without mutating
+    /// any worker's cache — for routers that need to score candidates
+    /// before committing to one.
+    pub fn matched_blocks(&self, tokens: &[u32]) -> Vec<u64> {
+        self.caches
+            .iter()
+            .map(|cache| cache.peek_matched_blocks(tokens))
+            .collect()
+    }
+
+    /// Routes `tokens` to `worker`, registering its blocks as resident
+    /// there and bumping that worker's load. Returns the tokens of
+    /// prefill this request saved by reusing that worker's cache.
+    pub fn route(&mut self, worker: usize, tokens: &[u32]) -> u64 {
+        self.load[worker] += 1;
+        self.caches[worker].lookup_and_insert(tokens)
+    }
+
+    /// Per-worker prefix-cache stats, for an end-of-run report on
+    /// aggregate hit rate and load balance across the fleet.
+    pub fn per_worker_stats(&self) -> Vec<PrefixCacheStats> {
+        self.caches.iter().map(|cache| cache.stats()).collect()
+    }
+
+    pub fn aggregate_hit_rate(&self) -> f64 {
+        let (matched, total) = self.caches.iter().fold((0u64, 0u64), |(m, t), cache| {
+            let stats = cache.stats();
+            (m + stats.matched_blocks, t + stats.total_blocks)
+        });
+        if total == 0 {
+            0.0
+        } else {
+            matched as f64 / total as f64
+        }
+    }
+
+    /// Coefficient of variation of per-worker load: `0.0` is perfectly
+    /// balanced, higher means more skewed.
+    pub fn load_imbalance(&self) -> f64 {
+        let n = self.load.len() as f64;
+        if n == 0.0 {
+            return 0.0;
+        }
+        let mean = self.load.iter().sum::<u64>() as f64 / n;
+        if mean == 0.0 {
+            return 0.0;
+        }
+        let variance = self
+            .load
+            .iter()
+            .map(|l| (*l as f64 - mean).powi(2))
+            .sum::<f64>()
+            / n;
+        variance.sqrt() / mean
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routing_to_a_warm_worker_saves_prefill() {
+        let mut fleet = WorkerFleet::new(2);
+        let tokens: Vec<u32> = (0..64).collect();
+        fleet.route(0, &tokens);
+        let saved = fleet.route(0, &tokens);
+        assert_eq!(saved, 64);
+        assert_eq!(fleet.route(1, &tokens), 0);
+    }
+
+    #[test]
+    fn load_imbalance_is_zero_when_balanced() {
+        let mut fleet = WorkerFleet::new(2);
+        let tokens: Vec<u32> = (0..16).collect();
+        fleet.route(0, &tokens);
+        fleet.route(1, &tokens);
+        assert_eq!(fleet.load_imbalance(), 0.0);
+    }
+}
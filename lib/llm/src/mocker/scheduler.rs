@@ -0,0 +1,493 @@
+//! Request admission queue for the mocker: chunked prefill, priority
+//! classes (strict or weighted fair share), and token-budget-driven
+//! decode preemption, matching the real scheduler's knobs closely enough
+//! to validate QoS and capacity policies before touching production.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use super::engine::MockRequest;
+
+/// Batching knobs mirroring the real engine's scheduler config:
+/// `max_num_batched_tokens` and `max_num_seqs` bound one iteration's
+/// work, and `scheduling_interval` is how often the scheduler runs.
+#[derive(Debug, Clone)]
+pub struct SchedulerLimits {
+    pub max_num_batched_tokens: u32,
+    pub max_num_seqs: u32,
+    pub scheduling_interval: Duration,
+}
+
+impl Default for SchedulerLimits {
+    fn default() -> Self {
+        Self {
+            max_num_batched_tokens: u32::MAX,
+            max_num_seqs: u32::MAX,
+            scheduling_interval: Duration::from_millis(1),
+        }
+    }
+}
+
+/// An admitted request paired with the simulated time it arrived, so
+/// downstream consumers (metrics, SLA tracking) can measure queueing
+/// delay.
+#[derive(Debug, Clone)]
+pub struct ScheduledRequest {
+    pub request: MockRequest,
+    pub arrival_time_s: f64,
+    pub prefix_group: Option<String>,
+    pub priority: Priority,
+}
+
+/// Request priority class. Ordered so that `Priority::Interactive >
+/// Priority::Batch` etc. under strict-priority scheduling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Priority {
+    Batch,
+    #[default]
+    Normal,
+    Interactive,
+}
+
+/// How the scheduler picks the next request to admit when multiple
+/// priority classes have work queued.
+#[derive(Debug, Clone, Default)]
+pub enum PriorityPolicy {
+    /// Always drain the highest non-empty priority class first.
+    #[default]
+    Strict,
+    /// Interleave classes proportionally to their configured weight,
+    /// so low-priority traffic still makes progress under load instead
+    /// of starving outright.
+    WeightedFairShare { weights: [u32; 3] },
+}
+
+/// Pluggable admission decision: given the requests currently queued,
+/// picks which one to serve next. Lets policies like shortest-job-first,
+/// deadline-aware, or per-tenant fairness be implemented out-of-tree
+/// against the same `Scheduler`, instead of only the built-in
+/// [`PriorityPolicy`] choices.
+pub trait SchedulingPolicy: std::fmt::Debug {
+    /// Returns the index into `candidates` to serve next, or `None` if
+    /// `candidates` is empty.
+    fn select(&mut self, candidates: &[ScheduledRequest]) -> Option<usize>;
+}
+
+/// FIFO admission queue. `Scheduler` owns the order requests are handed
+/// to the engine in; it does not itself run the engine.
+#[derive(Debug, Default)]
+pub struct Scheduler {
+    queue: VecDeque<ScheduledRequest>,
+    chunk_tokens: Option<u32>,
+    priority_policy: PriorityPolicy,
+    /// Running count of requests admitted per priority class this round,
+    /// used by weighted fair share to decide whose turn is next.
+    served_counts: [u32; 3],
+    limits: SchedulerLimits,
+    /// Overrides `priority_policy` entirely when set.
+    custom_policy: Option<Box<dyn SchedulingPolicy>>,
+}
+
+/// One slice of a request's prefill, sized to fit the scheduler's token
+/// budget for a single iteration. `is_final` tells the caller whether
+/// the request still has decode (and, for long prompts, more prefill
+/// chunks) ahead of it.
+#[derive(Debug, Clone)]
+pub struct PrefillChunk {
+    pub request: MockRequest,
+    pub prefix_group: Option<String>,
+    pub chunk_tokens: u32,
+    pub is_final: bool,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enables chunked prefill: long prompts are split into
+    /// `chunk_tokens`-token slices so they can interleave with decode
+    /// iterations for other in-flight requests, instead of blocking the
+    /// whole batch until the prompt finishes prefilling.
+    pub fn with_chunked_prefill(chunk_tokens: u32) -> Self {
+        assert!(chunk_tokens > 0, "chunk_tokens must be positive");
+        Self {
+            chunk_tokens: Some(chunk_tokens),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_priority_policy(policy: PriorityPolicy) -> Self {
+        Self {
+            priority_policy: policy,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_limits(limits: SchedulerLimits) -> Self {
+        Self {
+            limits,
+            ..Self::default()
+        }
+    }
+
+    /// Replaces the built-in priority policy with a custom
+    /// [`SchedulingPolicy`] implementation.
+    pub fn with_custom_policy(policy: Box<dyn SchedulingPolicy>) -> Self {
+        Self {
+            custom_policy: Some(policy),
+            ..Self::default()
+        }
+    }
+
+    pub fn limits(&self) -> &SchedulerLimits {
+        &self.limits
+    }
+
+    /// Pops requests for one scheduling iteration, stopping as soon as
+    /// admitting the next one would exceed `max_num_batched_tokens` or
+    /// `max_num_seqs`. A request whose prefill alone would blow the
+    /// token budget is still admitted alone (a real engine chunks it
+    /// instead of dropping it), matching `next_prefill_chunk`'s job.
+    pub fn drain_batch(&mut self) -> Vec<ScheduledRequest> {
+        let mut batch = Vec::new();
+        let mut batched_tokens: u64 = 0;
+        while (batch.len() as u32) < self.limits.max_num_seqs {
+            let Some(idx) = self.next_index() else { break };
+            let tokens = self.queue[idx].request.new_tokens as u64;
+            if !batch.is_empty()
+                && batched_tokens + tokens > self.limits.max_num_batched_tokens as u64
+            {
+                break;
+            }
+            let request = self.queue.remove(idx).unwrap();
+            self.served_counts[request.priority as usize] += 1;
+            batched_tokens += tokens;
+            batch.push(request);
+        }
+        batch
+    }
+
+    pub fn admit(&mut self, request: ScheduledRequest) {
+        self.queue.push_back(request);
+    }
+
+    /// Pops the next request according to the configured
+    /// [`PriorityPolicy`]. With `Strict`, the highest non-empty priority
+    /// class always wins; with `WeightedFairShare`, the class furthest
+    /// behind its weight's fair share wins, so low-priority traffic
+    /// still makes progress under sustained high-priority load.
+    pub fn next_request(&mut self) -> Option<ScheduledRequest> {
+        let idx = self.next_index()?;
+        let request = self.queue.remove(idx)?;
+        self.served_counts[request.priority as usize] += 1;
+        Some(request)
+    }
+
+    fn next_index(&mut self) -> Option<usize> {
+        if let Some(policy) = self.custom_policy.as_mut() {
+            let snapshot: Vec<ScheduledRequest> = self.queue.iter().cloned().collect();
+            return policy.select(&snapshot);
+        }
+        match self.priority_policy {
+            PriorityPolicy::Strict => (0..3)
+                .rev()
+                .find_map(|rank| self.queue.iter().position(|r| r.priority as usize == rank)),
+            PriorityPolicy::WeightedFairShare { weights } => {
+                let mut best: Option<(usize, f64)> = None;
+                for (rank, &weight) in weights.iter().enumerate() {
+                    if weight == 0 {
+                        continue;
+                    }
+                    if let Some(idx) = self.queue.iter().position(|r| r.priority as usize == rank)
+                    {
+                        let share = self.served_counts[rank] as f64 / weight as f64;
+                        let better = match best {
+                            None => true,
+                            Some((_, b)) => share < b,
+                        };
+                        if better {
+                            best = Some((idx, share));
+                        }
+                    }
+                }
+                best.map(|(idx, _)| idx)
+            }
+        }
+    }
+
+    /// Pops the next prefill chunk to run. With chunked prefill enabled
+    /// and a prompt larger than `chunk_tokens`, the remainder is
+    /// re-queued at the front so it's picked up again before later
+    /// requests, matching a real engine's run-to-completion-per-prompt
+    /// chunking.
+    pub fn next_prefill_chunk(&mut self) -> Option<PrefillChunk> {
+        let idx = self.next_index()?;
+        let mut scheduled = self.queue.remove(idx)?;
+        self.served_counts[scheduled.priority as usize] += 1;
+        let budget = self.chunk_tokens.unwrap_or(u32::MAX);
+        let new_tokens = scheduled.request.new_tokens;
+        if new_tokens <= budget {
+            return Some(PrefillChunk {
+                request: scheduled.request,
+                prefix_group: scheduled.prefix_group,
+                chunk_tokens: new_tokens,
+                is_final: true,
+            });
+        }
+        let this_chunk = budget;
+        let chunk = PrefillChunk {
+            request: MockRequest {
+                cached_tokens: scheduled.request.cached_tokens,
+                new_tokens: this_chunk,
+                max_output_tokens: scheduled.request.max_output_tokens,
+            },
+            prefix_group: scheduled.prefix_group.clone(),
+            chunk_tokens: this_chunk,
+            is_final: false,
+        };
+        scheduled.request.cached_tokens += this_chunk;
+        scheduled.request.new_tokens -= this_chunk;
+        self.queue.push_front(scheduled);
+        Some(chunk)
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
+
+/// A currently-decoding request tracked against the token budget, so it
+/// can be preempted if lower-priority than an incoming admission.
+#[derive(Debug, Clone)]
+pub struct ActiveDecode {
+    pub request_id: u64,
+    pub priority: Priority,
+    pub active_kv_tokens: u64,
+}
+
+/// Tracks in-flight decodes against a fixed KV token budget and evicts
+/// the lowest-priority ones (oldest first among ties) when a
+/// higher-or-equal-priority admission would otherwise exceed it.
+#[derive(Debug, Default)]
+pub struct DecodeBudget {
+    pub max_active_kv_tokens: u64,
+    active: Vec<ActiveDecode>,
+}
+
+impl DecodeBudget {
+    pub fn new(max_active_kv_tokens: u64) -> Self {
+        Self {
+            max_active_kv_tokens,
+            active: Vec::new(),
+        }
+    }
+
+    pub fn active_tokens(&self) -> u64 {
+        self.active.iter().map(|d| d.active_kv_tokens).sum()
+    }
+
+    pub fn track(&mut self, decode: ActiveDecode) {
+        self.active.push(decode);
+    }
+
+    /// Preempts active decodes at or below `incoming_priority`, lowest
+    /// priority first, until admitting `incoming_tokens` more would fit
+    /// the budget; stops early if only higher-priority decodes remain.
+    /// Returns the preempted requests (victims of recompute-or-swap,
+    /// decided by the caller) in eviction order.
+    pub fn preempt_to_fit(
+        &mut self,
+        incoming_tokens: u64,
+        incoming_priority: Priority,
+    ) -> Vec<ActiveDecode> {
+        let mut victims = Vec::new();
+        while self.active_tokens() + incoming_tokens > self.max_active_kv_tokens {
+            let Some(idx) = self
+                .active
+                .iter()
+                .enumerate()
+                .filter(|(_, d)| d.priority <= incoming_priority)
+                .min_by_key(|(_, d)| d.priority)
+                .map(|(idx, _)| idx)
+            else {
+                break;
+            };
+            victims.push(self.active.remove(idx));
+        }
+        victims
+    }
+}
+
+/// Example out-of-tree-style policy: always serves whichever queued
+/// request has the fewest new tokens to prefill.
+#[derive(Debug, Default)]
+pub struct ShortestJobFirst;
+
+impl SchedulingPolicy for ShortestJobFirst {
+    fn select(&mut self, candidates: &[ScheduledRequest]) -> Option<usize> {
+        candidates
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, r)| r.request.new_tokens)
+            .map(|(idx, _)| idx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn admits_in_fifo_order() {
+        let mut scheduler = Scheduler::new();
+        for i in 0..3 {
+            scheduler.admit(ScheduledRequest {
+                request: MockRequest {
+                    cached_tokens: 0,
+                    new_tokens: i,
+                    max_output_tokens: 10,
+                },
+                arrival_time_s: i as f64,
+                prefix_group: None,
+                priority: Priority::Normal,
+            });
+        }
+        assert_eq!(scheduler.next_request().unwrap().request.new_tokens, 0);
+        assert_eq!(scheduler.next_request().unwrap().request.new_tokens, 1);
+        assert_eq!(scheduler.len(), 1);
+    }
+
+    #[test]
+    fn chunked_prefill_splits_long_prompts() {
+        let mut scheduler = Scheduler::with_chunked_prefill(100);
+        scheduler.admit(ScheduledRequest {
+            request: MockRequest {
+                cached_tokens: 0,
+                new_tokens: 250,
+                max_output_tokens: 10,
+            },
+            arrival_time_s: 0.0,
+            prefix_group: None,
+            priority: Priority::Normal,
+        });
+
+        let chunk1 = scheduler.next_prefill_chunk().unwrap();
+        assert_eq!(chunk1.chunk_tokens, 100);
+        assert!(!chunk1.is_final);
+
+        let chunk2 = scheduler.next_prefill_chunk().unwrap();
+        assert_eq!(chunk2.chunk_tokens, 100);
+        assert!(!chunk2.is_final);
+
+        let chunk3 = scheduler.next_prefill_chunk().unwrap();
+        assert_eq!(chunk3.chunk_tokens, 50);
+        assert!(chunk3.is_final);
+
+        assert!(scheduler.is_empty());
+    }
+
+    fn admit_with(scheduler: &mut Scheduler, tokens: u32, priority: Priority) {
+        scheduler.admit(ScheduledRequest {
+            request: MockRequest {
+                cached_tokens: 0,
+                new_tokens: tokens,
+                max_output_tokens: 10,
+            },
+            arrival_time_s: 0.0,
+            prefix_group: None,
+            priority,
+        });
+    }
+
+    #[test]
+    fn strict_priority_drains_interactive_first() {
+        let mut scheduler = Scheduler::with_priority_policy(PriorityPolicy::Strict);
+        admit_with(&mut scheduler, 1, Priority::Batch);
+        admit_with(&mut scheduler, 2, Priority::Normal);
+        admit_with(&mut scheduler, 3, Priority::Interactive);
+
+        assert_eq!(scheduler.next_request().unwrap().request.new_tokens, 3);
+        assert_eq!(scheduler.next_request().unwrap().request.new_tokens, 2);
+        assert_eq!(scheduler.next_request().unwrap().request.new_tokens, 1);
+    }
+
+    #[test]
+    fn weighted_fair_share_eventually_serves_batch() {
+        let mut scheduler = Scheduler::with_priority_policy(PriorityPolicy::WeightedFairShare {
+            weights: [1, 0, 4],
+        });
+        admit_with(&mut scheduler, 1, Priority::Batch);
+        for _ in 0..8 {
+            admit_with(&mut scheduler, 2, Priority::Interactive);
+        }
+
+        let served: Vec<u32> = (0..9)
+            .map(|_| scheduler.next_request().unwrap().request.new_tokens)
+            .collect();
+        assert!(served.contains(&1), "batch class should not starve");
+    }
+
+    #[test]
+    fn drain_batch_respects_token_budget() {
+        let mut scheduler = Scheduler::with_limits(SchedulerLimits {
+            max_num_batched_tokens: 150,
+            max_num_seqs: u32::MAX,
+            scheduling_interval: Duration::from_millis(1),
+        });
+        admit_with(&mut scheduler, 100, Priority::Normal);
+        admit_with(&mut scheduler, 100, Priority::Normal);
+        admit_with(&mut scheduler, 10, Priority::Normal);
+
+        let batch = scheduler.drain_batch();
+        assert_eq!(batch.len(), 1);
+        assert_eq!(scheduler.len(), 2);
+    }
+
+    #[test]
+    fn drain_batch_respects_max_num_seqs() {
+        let mut scheduler = Scheduler::with_limits(SchedulerLimits {
+            max_num_batched_tokens: u32::MAX,
+            max_num_seqs: 2,
+            scheduling_interval: Duration::from_millis(1),
+        });
+        for _ in 0..5 {
+            admit_with(&mut scheduler, 1, Priority::Normal);
+        }
+        assert_eq!(scheduler.drain_batch().len(), 2);
+        assert_eq!(scheduler.len(), 3);
+    }
+
+    #[test]
+    fn custom_policy_overrides_priority_ordering() {
+        let mut scheduler = Scheduler::with_custom_policy(Box::new(ShortestJobFirst));
+        admit_with(&mut scheduler, 100, Priority::Interactive);
+        admit_with(&mut scheduler, 5, Priority::Batch);
+
+        assert_eq!(scheduler.next_request().unwrap().request.new_tokens, 5);
+    }
+
+    #[test]
+    fn decode_budget_preempts_lowest_priority_first() {
+        let mut budget = DecodeBudget::new(100);
+        budget.track(ActiveDecode {
+            request_id: 1,
+            priority: Priority::Batch,
+            active_kv_tokens: 60,
+        });
+        budget.track(ActiveDecode {
+            request_id: 2,
+            priority: Priority::Interactive,
+            active_kv_tokens: 30,
+        });
+
+        let victims = budget.preempt_to_fit(50, Priority::Interactive);
+        assert_eq!(victims.len(), 1);
+        assert_eq!(victims[0].request_id, 1);
+        assert_eq!(budget.active_tokens(), 30);
+    }
+}
@@ -0,0 +1,176 @@
+//! Output-length sampling for simulated requests. Response-length skew
+//! dominates scheduling behavior, so a single global assumption (e.g.
+//! "every request generates 128 tokens") produces misleading simulation
+//! results; this lets each workload class specify its own model.
+
+use super::rng::SeededRng;
+
+/// How a simulated request's output length is chosen.
+#[derive(Debug, Clone)]
+pub enum OutputLengthDistribution {
+    /// Every request in this class generates exactly `tokens` tokens.
+    /// Also how `ignore_eos` workloads are modeled: the model never
+    /// stops early, so the configured max is the actual length.
+    Fixed { tokens: u32 },
+    /// Uniformly distributed between `min` and `max` inclusive.
+    Uniform { min: u32, max: u32 },
+    /// Log-normal, parameterized in log-space like most real chat
+    /// workload length distributions (heavy right tail).
+    LogNormal { mu: f64, sigma: f64, max: u32 },
+    /// Sampled from an empirical histogram of observed lengths, e.g.
+    /// loaded from a production trace.
+    Empirical { buckets: Vec<(u32, f64)> },
+}
+
+/// How decoding is allowed to end: always run to the sampled/configured
+/// length (`ignore_eos`-style), or stop early if a stop token is drawn
+/// at each step. Stop-rate materially changes decode batch composition
+/// over time, since a batch with early-stopping requests constantly
+/// frees and refills slots while an `ignore_eos` batch doesn't.
+#[derive(Debug, Clone, Copy)]
+pub enum StopBehavior {
+    /// Never stop early; the sampled length from
+    /// [`OutputLengthDistribution`] is the actual length.
+    IgnoreEos,
+    /// At each decode step (after the first token), stop with
+    /// probability `per_step_stop_probability`, independent of step
+    /// index — a geometric-distribution approximation of "the model
+    /// emits EOS" that's cheap to simulate and matches real traces
+    /// reasonably well in aggregate.
+    StopToken {
+        per_step_stop_probability: f64,
+    },
+}
+
+impl OutputLengthDistribution {
+    pub fn sample(&self, rng: &mut SeededRng) -> u32 {
+        match self {
+            OutputLengthDistribution::Fixed { tokens } => *tokens,
+            OutputLengthDistribution::Uniform { min, max } => {
+                if min >= max {
+                    *min
+                } else {
+                    rng.gen_range_u32(*min..*max + 1)
+                }
+            }
+            OutputLengthDistribution::LogNormal { mu, sigma, max } => {
+                // Box-Muller via two uniforms, since `rand_distr` isn't a
+                // dependency here and the mocker doesn't need anything
+                // fancier than a standard normal draw.
+                let u1 = rng.gen_range(1e-9..1.0);
+                let u2 = rng.gen_range(0.0..1.0);
+                let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+                let sample = (mu + sigma * z).exp();
+                (sample.round() as u32).clamp(1, *max)
+            }
+            OutputLengthDistribution::Empirical { buckets } => {
+                sample_empirical(buckets, rng)
+            }
+        }
+    }
+
+    /// Samples a target length, then applies `stop_behavior` to decide
+    /// the actual number of tokens generated before stopping (which may
+    /// be shorter than the target, never longer).
+    pub fn sample_with_stop(&self, stop_behavior: StopBehavior, rng: &mut SeededRng) -> u32 {
+        let target = self.sample(rng);
+        match stop_behavior {
+            StopBehavior::IgnoreEos => target,
+            StopBehavior::StopToken {
+                per_step_stop_probability,
+            } => {
+                for step in 1..=target {
+                    if rng.gen_bool(per_step_stop_probability) {
+                        return step;
+                    }
+                }
+                target
+            }
+        }
+    }
+}
+
+fn sample_empirical(buckets: &[(u32, f64)], rng: &mut SeededRng) -> u32 {
+    let total: f64 = buckets.iter().map(|(_, w)| w).sum();
+    if total <= 0.0 {
+        return buckets.first().map(|(tokens, _)| *tokens).unwrap_or(0);
+    }
+    let mut draw = rng.gen_range(0.0..total);
+    for (tokens, weight) in buckets {
+        if draw < *weight {
+            return *tokens;
+        }
+        draw -= weight;
+    }
+    buckets.last().map(|(tokens, _)| *tokens).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_always_returns_configured_length() {
+        let mut rng = SeededRng::new(1);
+        let dist = OutputLengthDistribution::Fixed { tokens: 256 };
+        for _ in 0..5 {
+            assert_eq!(dist.sample(&mut rng), 256);
+        }
+    }
+
+    #[test]
+    fn uniform_stays_in_bounds() {
+        let mut rng = SeededRng::new(7);
+        let dist = OutputLengthDistribution::Uniform { min: 10, max: 20 };
+        for _ in 0..50 {
+            let v = dist.sample(&mut rng);
+            assert!((10..=20).contains(&v));
+        }
+    }
+
+    #[test]
+    fn lognormal_stays_under_max() {
+        let mut rng = SeededRng::new(3);
+        let dist = OutputLengthDistribution::LogNormal {
+            mu: 5.0,
+            sigma: 1.0,
+            max: 512,
+        };
+        for _ in 0..50 {
+            let v = dist.sample(&mut rng);
+            assert!((1..=512).contains(&v));
+        }
+    }
+
+    #[test]
+    fn ignore_eos_never_stops_early() {
+        let mut rng = SeededRng::new(5);
+        let dist = OutputLengthDistribution::Fixed { tokens: 100 };
+        assert_eq!(dist.sample_with_stop(StopBehavior::IgnoreEos, &mut rng), 100);
+    }
+
+    #[test]
+    fn stop_token_can_cut_generation_short() {
+        let mut rng = SeededRng::new(5);
+        let dist = OutputLengthDistribution::Fixed { tokens: 1000 };
+        let stop = StopBehavior::StopToken {
+            per_step_stop_probability: 0.5,
+        };
+        let lengths: Vec<u32> = (0..20)
+            .map(|_| dist.sample_with_stop(stop, &mut rng))
+            .collect();
+        assert!(lengths.iter().any(|&l| l < 1000));
+    }
+
+    #[test]
+    fn empirical_only_returns_bucketed_values() {
+        let mut rng = SeededRng::new(9);
+        let dist = OutputLengthDistribution::Empirical {
+            buckets: vec![(10, 1.0), (20, 1.0), (30, 1.0)],
+        };
+        for _ in 0..50 {
+            let v = dist.sample(&mut rng);
+            assert!([10, 20, 30].contains(&v));
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! OpenAI-compatible HTTP frontend for [`MockEngine`]: serves
+//! `/v1/chat/completions` and `/v1/completions` with realistic
+//! streaming pacing, so client tooling, gateways, and load tests can
+//! run against a GPU-free endpoint that behaves like a loaded Dynamo
+//! worker. `/v1/completions` is routed to the chat handler, so it takes
+//! the chat request body (`messages`) and answers with chat-completion
+//! objects; a `prompt`-only completions body is rejected.
+
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::extract::State;
+use axum::response::sse::{Event, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use futures::stream::Stream;
+use serde::{Deserialize, Serialize};
+
+use super::engine::{MockEngine, MockRequest};
+
+#[derive(Debug, Deserialize)]
+pub struct ChatCompletionRequest {
+    pub model: String,
+    pub messages: Vec<serde_json::Value>,
+    #[serde(default)]
+    pub stream: bool,
+    #[serde(default = "default_max_tokens")]
+    pub max_tokens: u32,
+}
+
+fn default_max_tokens() -> u32 {
+    16
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChatCompletionChoice {
+    pub index: u32,
+    pub message: ChatCompletionMessage,
+    pub finish_reason: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChatCompletionMessage {
+    pub role: String,
+    pub content: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChatCompletionResponse {
+    pub id: String,
+    pub object: String,
+    pub model: String,
+    pub choices: Vec<ChatCompletionChoice>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatCompletionChunkDelta {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatCompletionChunkChoice {
+    index: u32,
+    delta: ChatCompletionChunkDelta,
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatCompletionChunk {
+    id: String,
+    object: String,
+    model: String,
+    choices: Vec<ChatCompletionChunkChoice>,
+}
+
+/// Shared state for the mock server: the engine that decides timing.
+/// The prompt length fed to it is a rough estimate from message text
+/// (`estimate_prompt_tokens`; a real server would tokenize, the mock
+/// only needs a token count).
+#[derive(Clone)]
+pub struct ServerState {
+    engine: Arc<Mutex<MockEngine>>,
+}
+
+impl ServerState {
+    pub fn new(engine: MockEngine) -> Self {
+        Self {
+            engine: Arc::new(Mutex::new(engine)),
+        }
+    }
+}
+
+fn estimate_prompt_tokens(messages: &[serde_json::Value]) -> u32 {
+    messages
+        .iter()
+        .filter_map(|m| m.get("content").and_then(|c| c.as_str()))
+        .map(|s| (s.len() / 4).max(1) as u32)
+        .sum()
+}
+
+pub fn router(state: ServerState) -> Router {
+    Router::new()
+        .route("/v1/chat/completions", post(chat_completions))
+        .route("/v1/completions", post(chat_completions))
+        .with_state(state)
+}
+
+async fn chat_completions(
+    State(state): State<ServerState>,
+    Json(request): Json<ChatCompletionRequest>,
+) -> Response {
+    let prompt_tokens = estimate_prompt_tokens(&request.messages);
+    let mock_request = MockRequest {
+        cached_tokens: 0,
+        new_tokens: prompt_tokens,
+        max_output_tokens: request.max_tokens,
+    };
+
+    let (prefill_delay, decode_delay) = {
+        let engine = state.engine.lock().unwrap();
+        (
+            engine.prefill_latency(&mock_request),
+            engine.decode_step_latency(1, prompt_tokens as u64),
+        )
+    };
+    tokio::time::sleep(prefill_delay).await;
+
+    let completion_id = format!("mockcmpl-{}", uuid::Uuid::new_v4());
+
+    if request.stream {
+        let stream = stream_chunks(completion_id, request.model, request.max_tokens, decode_delay);
+        Sse::new(stream).into_response()
+    } else {
+        for _ in 0..request.max_tokens {
+            tokio::time::sleep(decode_delay).await;
+        }
+        Json(ChatCompletionResponse {
+            id: completion_id,
+            object: "chat.completion".to_string(),
+            model: request.model,
+            choices: vec![ChatCompletionChoice {
+                index: 0,
+                message: ChatCompletionMessage {
+                    role: "assistant".to_string(),
+                    content: "mocked response".to_string(),
+                },
+                finish_reason: "length".to_string(),
+            }],
+        })
+        .into_response()
+    }
+}
+
+fn stream_chunks(
+    id: String,
+    model: String,
+    max_tokens: u32,
+    decode_delay: Duration,
+) -> impl Stream<Item = Result<Event, Infallible>> {
+    async_stream::stream! {
+        for i in 0..max_tokens {
+            tokio::time::sleep(decode_delay).await;
+            let finish_reason = if i + 1 == max_tokens { Some("length".to_string()) } else { None };
+            let chunk = ChatCompletionChunk {
+                id: id.clone(),
+                object: "chat.completion.chunk".to_string(),
+                model: model.clone(),
+                choices: vec![ChatCompletionChunkChoice {
+                    index: 0,
+                    delta: ChatCompletionChunkDelta { content: Some("x".to_string()) },
+                    finish_reason,
+                }],
+            };
+            let data = serde_json::to_string(&chunk).unwrap_or_default();
+            yield Ok(Event::default().data(data));
+        }
+        yield Ok(Event::default().data("[DONE]"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimates_prompt_tokens_from_message_content() {
+        let messages = vec![serde_json::json!({"role": "user", "content": "hello world"})];
+        assert!(estimate_prompt_tokens(&messages) > 0);
+    }
+}
@@ -0,0 +1,80 @@
+//! Per-workload-class SLA targets and violation tracking, so scheduler
+//! and planner policies can be optimized directly against SLO
+//! attainment instead of only raw latency averages.
+
+use std::time::Duration;
+
+use super::metrics::Histogram;
+
+/// TTFT and per-token latency targets for one workload class.
+#[derive(Debug, Clone)]
+pub struct SlaTarget {
+    pub name: String,
+    pub max_ttft: Duration,
+    pub max_inter_token_latency: Duration,
+}
+
+/// Running violation counts and latency distributions for one
+/// [`SlaTarget`], built up as requests complete.
+#[derive(Debug, Default)]
+pub struct SlaReport {
+    pub total_requests: u64,
+    pub ttft_violations: u64,
+    pub itl_violations: u64,
+    pub ttft_ms: Histogram,
+    pub itl_ms: Histogram,
+}
+
+impl SlaReport {
+    /// Records one completed request's observed TTFT and worst
+    /// inter-token latency against `target`.
+    pub fn record(&mut self, target: &SlaTarget, ttft: Duration, worst_itl: Duration) {
+        self.total_requests += 1;
+        self.ttft_ms.observe(ttft.as_secs_f64() * 1000.0);
+        self.itl_ms.observe(worst_itl.as_secs_f64() * 1000.0);
+        if ttft > target.max_ttft {
+            self.ttft_violations += 1;
+        }
+        if worst_itl > target.max_inter_token_latency {
+            self.itl_violations += 1;
+        }
+    }
+
+    pub fn ttft_violation_rate(&self) -> f64 {
+        if self.total_requests == 0 {
+            0.0
+        } else {
+            self.ttft_violations as f64 / self.total_requests as f64
+        }
+    }
+
+    pub fn itl_violation_rate(&self) -> f64 {
+        if self.total_requests == 0 {
+            0.0
+        } else {
+            self.itl_violations as f64 / self.total_requests as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn violations_counted_only_when_target_exceeded() {
+        let target = SlaTarget {
+            name: "interactive".to_string(),
+            max_ttft: Duration::from_millis(200),
+            max_inter_token_latency: Duration::from_millis(50),
+        };
+        let mut report = SlaReport::default();
+        report.record(&target, Duration::from_millis(100), Duration::from_millis(20));
+        report.record(&target, Duration::from_millis(300), Duration::from_millis(20));
+
+        assert_eq!(report.total_requests, 2);
+        assert_eq!(report.ttft_violations, 1);
+        assert_eq!(report.itl_violations, 0);
+        assert!((report.ttft_violation_rate() - 0.5).abs() < 1e-9);
+    }
+}
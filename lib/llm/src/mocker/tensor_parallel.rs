@@ -0,0 +1,86 @@
+//! Tensor-parallel worker simulation: a TP-degree parameter that scales
+//! per-GPU KV capacity and adds allreduce latency per decode step, so
+//! simulations can compare TP2/TP4/TP8 layouts for the same traffic
+//! without needing a multi-GPU host.
+
+use std::time::Duration;
+
+/// Cost model for one tensor-parallel rank group.
+#[derive(Debug, Clone)]
+pub struct TensorParallelConfig {
+    pub degree: u32,
+    /// Total model KV capacity (tokens) when run with TP degree 1; each
+    /// additional rank divides the model's weight footprint and frees up
+    /// the difference for more KV cache, matching a real deployment
+    /// where higher TP leaves more headroom per GPU.
+    pub kv_capacity_at_tp1: u64,
+    /// Fixed interconnect latency for one allreduce across the TP group,
+    /// e.g. over NVLink.
+    pub allreduce_latency: Duration,
+    /// Per-rank bytes added to the allreduce payload per decode step
+    /// (the activations that must be synchronized).
+    pub allreduce_bytes_per_rank: u64,
+    pub allreduce_bandwidth_gbps: f64,
+}
+
+impl TensorParallelConfig {
+    pub fn new(degree: u32) -> Self {
+        assert!(degree >= 1, "TP degree must be at least 1");
+        Self {
+            degree,
+            kv_capacity_at_tp1: 1_000_000,
+            allreduce_latency: Duration::from_micros(20),
+            allreduce_bytes_per_rank: 4 * 1024 * 1024,
+            allreduce_bandwidth_gbps: 300.0,
+        }
+    }
+
+    /// KV capacity available per rank once the model's own weights are
+    /// sharded `degree`-ways, freeing up roughly proportional extra
+    /// memory for cache. Matches the rough intuition that doubling TP
+    /// roughly doubles usable KV capacity per GPU, though not exactly
+    /// since activations don't shard as cleanly as weights.
+    pub fn kv_capacity_per_rank(&self) -> u64 {
+        self.kv_capacity_at_tp1 + self.kv_capacity_at_tp1 * (self.degree as u64 - 1) / 2
+    }
+
+    /// Extra latency one decode step pays for rank synchronization.
+    /// Grows with `degree` because a ring/tree allreduce's latency term
+    /// scales with the number of participants even though its bandwidth
+    /// term amortizes across them.
+    pub fn allreduce_step_latency(&self) -> Duration {
+        if self.degree <= 1 {
+            return Duration::ZERO;
+        }
+        let bandwidth_bytes_per_s = self.allreduce_bandwidth_gbps * 1e9 / 8.0;
+        let payload_bytes = self.allreduce_bytes_per_rank * self.degree as u64;
+        let transfer_s = payload_bytes as f64 / bandwidth_bytes_per_s;
+        let hops = (self.degree as f64).log2().ceil().max(1.0);
+        self.allreduce_latency.mul_f64(hops) + Duration::from_secs_f64(transfer_s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn higher_tp_gives_more_kv_capacity_per_rank() {
+        let tp2 = TensorParallelConfig::new(2);
+        let tp8 = TensorParallelConfig::new(8);
+        assert!(tp8.kv_capacity_per_rank() > tp2.kv_capacity_per_rank());
+    }
+
+    #[test]
+    fn tp1_has_no_allreduce_cost() {
+        let tp1 = TensorParallelConfig::new(1);
+        assert_eq!(tp1.allreduce_step_latency(), Duration::ZERO);
+    }
+
+    #[test]
+    fn higher_tp_costs_more_allreduce_latency() {
+        let tp2 = TensorParallelConfig::new(2);
+        let tp8 = TensorParallelConfig::new(8);
+        assert!(tp8.allreduce_step_latency() > tp2.allreduce_step_latency());
+    }
+}
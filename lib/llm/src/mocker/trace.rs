@@ -0,0 +1,67 @@
+//! Workload trace replay: read request traces (mooncake/ShareGPT-style
+//! JSONL) and inject them into the scheduler with faithful inter-arrival
+//! timing instead of a synthetic arrival process.
+
+use serde::{Deserialize, Serialize};
+use std::io::BufRead;
+use std::path::Path;
+
+/// One line of a trace file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceEntry {
+    /// Arrival time in seconds, relative to the start of the trace.
+    pub arrival_time_s: f64,
+    pub input_length: u32,
+    pub output_length: u32,
+    /// Requests sharing a group id are assumed to share a KV prefix, for
+    /// exercising prefix-reuse scheduling.
+    #[serde(default)]
+    pub prefix_group: Option<String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TraceError {
+    #[error("io error reading trace: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("malformed trace line {line}: {source}")]
+    Parse {
+        line: usize,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+/// A trace loaded into memory, sorted by arrival time so replay can walk
+/// it in order.
+pub struct Trace {
+    pub entries: Vec<TraceEntry>,
+}
+
+impl Trace {
+    pub fn load(path: &Path) -> Result<Self, TraceError> {
+        let file = std::fs::File::open(path)?;
+        let reader = std::io::BufReader::new(file);
+        let mut entries = Vec::new();
+        for (idx, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry: TraceEntry = serde_json::from_str(&line)
+                .map_err(|source| TraceError::Parse { line: idx + 1, source })?;
+            entries.push(entry);
+        }
+        entries.sort_by(|a, b| a.arrival_time_s.total_cmp(&b.arrival_time_s));
+        Ok(Self { entries })
+    }
+
+    /// Inter-arrival gaps between consecutive entries, in seconds. The
+    /// replay driver sleeps (or advances the simulated clock) by these
+    /// between injecting requests.
+    pub fn inter_arrival_gaps(&self) -> Vec<f64> {
+        self.entries
+            .windows(2)
+            .map(|pair| (pair[1].arrival_time_s - pair[0].arrival_time_s).max(0.0))
+            .collect()
+    }
+}
@@ -0,0 +1,109 @@
+//! Cold-start modeling for the mocker: model-load time, a first-request
+//! warmup penalty, and periodic CUDA-graph capture pauses, so
+//! autoscaling simulations account for the real cost of spinning up a
+//! new worker instead of treating it as instantly ready.
+
+use std::time::Duration;
+
+/// Cold-start cost model for one worker's lifecycle.
+#[derive(Debug, Clone)]
+pub struct WarmupProfile {
+    /// Time to load model weights onto the GPU before any request can
+    /// be served at all.
+    pub model_load_time: Duration,
+    /// Extra latency the very first request pays beyond normal
+    /// prefill/decode, e.g. for lazy kernel compilation.
+    pub first_request_penalty: Duration,
+    /// How often a CUDA-graph re-capture pause occurs (e.g. on batch
+    /// shape changes), and how long each pause lasts.
+    pub graph_capture_interval: Duration,
+    pub graph_capture_pause: Duration,
+}
+
+impl Default for WarmupProfile {
+    fn default() -> Self {
+        Self {
+            model_load_time: Duration::from_secs(30),
+            first_request_penalty: Duration::from_millis(500),
+            graph_capture_interval: Duration::from_secs(60),
+            graph_capture_pause: Duration::from_millis(200),
+        }
+    }
+}
+
+/// Tracks a single worker's progress through its cold-start lifecycle:
+/// still loading, warmed up but not yet graph-stable, or fully steady.
+#[derive(Debug)]
+pub struct WorkerLifecycle {
+    profile: WarmupProfile,
+    started_at_s: f64,
+    served_first_request: bool,
+    last_graph_capture_s: f64,
+}
+
+impl WorkerLifecycle {
+    pub fn new(profile: WarmupProfile, started_at_s: f64) -> Self {
+        Self {
+            profile,
+            started_at_s,
+            served_first_request: false,
+            last_graph_capture_s: started_at_s,
+        }
+    }
+
+    /// Whether the worker has finished loading and can accept requests
+    /// as of simulated time `now_s`.
+    pub fn is_ready(&self, now_s: f64) -> bool {
+        now_s - self.started_at_s >= self.profile.model_load_time.as_secs_f64()
+    }
+
+    /// Extra latency `now_s`'s request should pay on top of normal
+    /// prefill/decode timing: the one-time first-request penalty, plus
+    /// a graph-capture pause if one is due. Calling this advances the
+    /// lifecycle's internal bookkeeping (first-request flag, last
+    /// capture time), so it should be called once per admitted request
+    /// in arrival order.
+    pub fn extra_latency(&mut self, now_s: f64) -> Duration {
+        let mut extra = Duration::ZERO;
+        if !self.served_first_request {
+            extra += self.profile.first_request_penalty;
+            self.served_first_request = true;
+        }
+        if now_s - self.last_graph_capture_s >= self.profile.graph_capture_interval.as_secs_f64() {
+            extra += self.profile.graph_capture_pause;
+            self.last_graph_capture_s = now_s;
+        }
+        extra
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn worker_not_ready_until_model_load_completes() {
+        let lifecycle = WorkerLifecycle::new(WarmupProfile::default(), 0.0);
+        assert!(!lifecycle.is_ready(10.0));
+        assert!(lifecycle.is_ready(30.0));
+    }
+
+    #[test]
+    fn only_first_request_pays_warmup_penalty() {
+        let mut lifecycle = WorkerLifecycle::new(WarmupProfile::default(), 0.0);
+        let first = lifecycle.extra_latency(30.0);
+        let second = lifecycle.extra_latency(31.0);
+        assert!(first >= Duration::from_millis(500));
+        assert!(second < Duration::from_millis(500));
+    }
+
+    #[test]
+    fn graph_capture_pause_recurs_on_interval() {
+        let mut lifecycle = WorkerLifecycle::new(WarmupProfile::default(), 0.0);
+        lifecycle.extra_latency(30.0);
+        let no_pause = lifecycle.extra_latency(31.0);
+        assert_eq!(no_pause, Duration::ZERO);
+        let pause = lifecycle.extra_latency(91.0);
+        assert_eq!(pause, Duration::from_millis(200));
+    }
+}
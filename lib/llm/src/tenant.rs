@@ -0,0 +1,402 @@
+//! Multi-tenant quota tracking: per-tenant limits on concurrent
+//! requests, tokens/min, and KV-block usage, tracked under one registry
+//! so a noisy tenant on a shared deployment can't starve the others.
+//! Mirrors the token-bucket-plus-concurrency-cap shape of
+//! `dynamo_runtime::admission`, but keyed by tenant rather than by
+//! `(model, api key)`, and extended with a KV-block budget. Actually
+//! admitting a request or reserving KV blocks against the budget is left
+//! to the caller — [`QuotaRegistry::try_admit`] and
+//! [`QuotaRegistry::try_reserve_kv_blocks`] are the seam the request path
+//! and the block manager's offload path are each expected to call into;
+//! this module only tracks and enforces the numbers once they do. Usage
+//! is exposed via [`QuotaRegistry::usage_snapshot`] for an admin surface
+//! to poll.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::extract::State;
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::Serialize;
+
+/// Identifies the tenant a request or KV allocation belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct TenantId(pub String);
+
+impl From<&str> for TenantId {
+    fn from(s: &str) -> Self {
+        Self(s.to_string())
+    }
+}
+
+impl From<String> for TenantId {
+    fn from(s: String) -> Self {
+        Self(s)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum QuotaError {
+    #[error("tenant {0:?} has reached its concurrent request limit")]
+    ConcurrencyLimitReached(TenantId),
+    #[error("tenant {0:?} has exhausted its tokens/min budget, retry after {1:?}")]
+    TokenBudgetExhausted(TenantId, Duration),
+    #[error("tenant {0:?} has exhausted its KV-block budget")]
+    KvBlockBudgetExhausted(TenantId),
+}
+
+/// Limits assigned to one tenant.
+#[derive(Debug, Clone, Copy)]
+pub struct TenantQuota {
+    pub max_concurrent_requests: u32,
+    pub max_tokens_per_min: u64,
+    pub max_kv_blocks: u64,
+}
+
+impl Default for TenantQuota {
+    fn default() -> Self {
+        Self {
+            max_concurrent_requests: 16,
+            max_tokens_per_min: 60_000,
+            max_kv_blocks: 4096,
+        }
+    }
+}
+
+/// Token bucket over a tokens/min budget, refilled lazily on each
+/// acquire attempt rather than on a background timer.
+struct TokenBudget {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBudget {
+    fn new(max_tokens_per_min: u64) -> Self {
+        Self {
+            tokens: max_tokens_per_min as f64,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn try_acquire(&mut self, tokens: u64, max_tokens_per_min: u64) -> Result<(), Duration> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        let per_second = max_tokens_per_min as f64 / 60.0;
+        self.tokens = (self.tokens + elapsed * per_second).min(max_tokens_per_min as f64);
+        self.last_refill = now;
+
+        if self.tokens >= tokens as f64 {
+            self.tokens -= tokens as f64;
+            Ok(())
+        } else {
+            let deficit = tokens as f64 - self.tokens;
+            Err(Duration::from_secs_f64(
+                deficit / per_second.max(f64::MIN_POSITIVE),
+            ))
+        }
+    }
+}
+
+struct TenantState {
+    quota: Mutex<TenantQuota>,
+    budget: Mutex<TokenBudget>,
+    in_flight: AtomicI64,
+    kv_blocks_in_use: AtomicU64,
+}
+
+impl TenantState {
+    fn new(quota: TenantQuota) -> Self {
+        Self {
+            budget: Mutex::new(TokenBudget::new(quota.max_tokens_per_min)),
+            quota: Mutex::new(quota),
+            in_flight: AtomicI64::new(0),
+            kv_blocks_in_use: AtomicU64::new(0),
+        }
+    }
+}
+
+/// Releases the concurrency slot a successful admission took, once the
+/// guard drops at the end of the request.
+pub struct RequestGuard {
+    state: Arc<TenantState>,
+}
+
+impl Drop for RequestGuard {
+    fn drop(&mut self) {
+        self.state.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Releases a reserved KV-block allocation once the guard drops.
+pub struct KvBlockGuard {
+    state: Arc<TenantState>,
+    blocks: u64,
+}
+
+impl Drop for KvBlockGuard {
+    fn drop(&mut self) {
+        self.state
+            .kv_blocks_in_use
+            .fetch_sub(self.blocks, Ordering::Relaxed);
+    }
+}
+
+/// A tenant's usage at a point in time, for the admin API.
+#[derive(Debug, Clone, Serialize)]
+pub struct TenantUsageSnapshot {
+    pub tenant: TenantId,
+    pub in_flight_requests: i64,
+    pub kv_blocks_in_use: u64,
+    pub quota: TenantQuotaSnapshot,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct TenantQuotaSnapshot {
+    pub max_concurrent_requests: u32,
+    pub max_tokens_per_min: u64,
+    pub max_kv_blocks: u64,
+}
+
+impl From<TenantQuota> for TenantQuotaSnapshot {
+    fn from(q: TenantQuota) -> Self {
+        Self {
+            max_concurrent_requests: q.max_concurrent_requests,
+            max_tokens_per_min: q.max_tokens_per_min,
+            max_kv_blocks: q.max_kv_blocks,
+        }
+    }
+}
+
+/// Per-tenant quota state, created lazily on first use so a tenant
+/// doesn't need to be provisioned ahead of time.
+#[derive(Default)]
+pub struct QuotaRegistry {
+    tenants: Mutex<HashMap<TenantId, Arc<TenantState>>>,
+    default_quota: Mutex<TenantQuota>,
+}
+
+impl QuotaRegistry {
+    pub fn new(default_quota: TenantQuota) -> Self {
+        Self {
+            tenants: Mutex::new(HashMap::new()),
+            default_quota: Mutex::new(default_quota),
+        }
+    }
+
+    fn state(&self, tenant: &TenantId) -> Arc<TenantState> {
+        let mut tenants = self.tenants.lock().unwrap();
+        tenants
+            .entry(tenant.clone())
+            .or_insert_with(|| Arc::new(TenantState::new(*self.default_quota.lock().unwrap())))
+            .clone()
+    }
+
+    /// Overrides the quota for one tenant immediately; already-admitted
+    /// requests and reserved KV blocks are unaffected.
+    pub fn set_quota(&self, tenant: &TenantId, quota: TenantQuota) {
+        let state = self.state(tenant);
+        *state.quota.lock().unwrap() = quota;
+    }
+
+    /// Admits one request for `tenant` if it's under its concurrency cap
+    /// and has enough tokens/min budget for `estimated_tokens`.
+    pub fn try_admit(
+        &self,
+        tenant: &TenantId,
+        estimated_tokens: u64,
+    ) -> Result<RequestGuard, QuotaError> {
+        let state = self.state(tenant);
+        let quota = *state.quota.lock().unwrap();
+
+        let in_flight = state.in_flight.fetch_add(1, Ordering::Relaxed) + 1;
+        if in_flight > quota.max_concurrent_requests as i64 {
+            state.in_flight.fetch_sub(1, Ordering::Relaxed);
+            return Err(QuotaError::ConcurrencyLimitReached(tenant.clone()));
+        }
+
+        if let Err(retry_after) = state
+            .budget
+            .lock()
+            .unwrap()
+            .try_acquire(estimated_tokens, quota.max_tokens_per_min)
+        {
+            state.in_flight.fetch_sub(1, Ordering::Relaxed);
+            return Err(QuotaError::TokenBudgetExhausted(
+                tenant.clone(),
+                retry_after,
+            ));
+        }
+
+        Ok(RequestGuard { state })
+    }
+
+    /// Reserves `blocks` KV blocks against `tenant`'s budget, for the
+    /// block manager to call before onboarding a sequence's cache.
+    pub fn try_reserve_kv_blocks(
+        &self,
+        tenant: &TenantId,
+        blocks: u64,
+    ) -> Result<KvBlockGuard, QuotaError> {
+        let state = self.state(tenant);
+        let quota = *state.quota.lock().unwrap();
+
+        let in_use = state.kv_blocks_in_use.fetch_add(blocks, Ordering::Relaxed) + blocks;
+        if in_use > quota.max_kv_blocks {
+            state.kv_blocks_in_use.fetch_sub(blocks, Ordering::Relaxed);
+            return Err(QuotaError::KvBlockBudgetExhausted(tenant.clone()));
+        }
+
+        Ok(KvBlockGuard { state, blocks })
+    }
+
+    pub fn usage_snapshot(&self, tenant: &TenantId) -> TenantUsageSnapshot {
+        let state = self.state(tenant);
+        let quota = *state.quota.lock().unwrap();
+        TenantUsageSnapshot {
+            tenant: tenant.clone(),
+            in_flight_requests: state.in_flight.load(Ordering::Relaxed),
+            kv_blocks_in_use: state.kv_blocks_in_use.load(Ordering::Relaxed),
+            quota: quota.into(),
+        }
+    }
+
+    /// Usage for every tenant that has been seen so far, for the admin
+    /// usage endpoint.
+    pub fn usage_snapshot_all(&self) -> Vec<TenantUsageSnapshot> {
+        self.tenants
+            .lock()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|tenant| self.usage_snapshot(&tenant))
+            .collect()
+    }
+}
+
+async fn usage_handler(
+    State(registry): State<Arc<QuotaRegistry>>,
+) -> Json<Vec<TenantUsageSnapshot>> {
+    Json(registry.usage_snapshot_all())
+}
+
+/// `GET /admin/tenants/usage`: usage and quota for every tenant seen so
+/// far, for an operator dashboard or alerting on who's close to their
+/// budget.
+pub fn admin_router(registry: Arc<QuotaRegistry>) -> Router {
+    Router::new()
+        .route("/admin/tenants/usage", get(usage_handler))
+        .with_state(registry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn admits_up_to_concurrency_limit_then_rejects() {
+        let registry = QuotaRegistry::new(TenantQuota {
+            max_concurrent_requests: 1,
+            max_tokens_per_min: 1_000_000,
+            max_kv_blocks: 100,
+        });
+        let tenant = TenantId::from("acme-corp");
+
+        let guard = registry.try_admit(&tenant, 10).unwrap();
+        assert!(matches!(
+            registry.try_admit(&tenant, 10),
+            Err(QuotaError::ConcurrencyLimitReached(_))
+        ));
+
+        drop(guard);
+        assert!(registry.try_admit(&tenant, 10).is_ok());
+    }
+
+    #[test]
+    fn token_budget_is_exhausted_by_large_requests() {
+        let registry = QuotaRegistry::new(TenantQuota {
+            max_concurrent_requests: 100,
+            max_tokens_per_min: 100,
+            max_kv_blocks: 100,
+        });
+        let tenant = TenantId::from("acme-corp");
+
+        assert!(registry.try_admit(&tenant, 60).is_ok());
+        assert!(matches!(
+            registry.try_admit(&tenant, 60),
+            Err(QuotaError::TokenBudgetExhausted(_, _))
+        ));
+    }
+
+    #[test]
+    fn tenants_get_independent_quotas() {
+        let registry = QuotaRegistry::new(TenantQuota {
+            max_concurrent_requests: 1,
+            max_tokens_per_min: 1_000_000,
+            max_kv_blocks: 100,
+        });
+        let _guard = registry.try_admit(&TenantId::from("tenant-a"), 10).unwrap();
+        assert!(registry.try_admit(&TenantId::from("tenant-b"), 10).is_ok());
+    }
+
+    #[test]
+    fn kv_block_budget_rejects_past_limit() {
+        let registry = QuotaRegistry::new(TenantQuota {
+            max_concurrent_requests: 10,
+            max_tokens_per_min: 1_000_000,
+            max_kv_blocks: 10,
+        });
+        let tenant = TenantId::from("acme-corp");
+
+        let guard = registry.try_reserve_kv_blocks(&tenant, 10).unwrap();
+        assert!(matches!(
+            registry.try_reserve_kv_blocks(&tenant, 1),
+            Err(QuotaError::KvBlockBudgetExhausted(_))
+        ));
+
+        drop(guard);
+        assert!(registry.try_reserve_kv_blocks(&tenant, 1).is_ok());
+    }
+
+    #[test]
+    fn usage_snapshot_reflects_in_flight_and_kv_usage() {
+        let registry = QuotaRegistry::new(TenantQuota::default());
+        let tenant = TenantId::from("acme-corp");
+        let _request_guard = registry.try_admit(&tenant, 10).unwrap();
+        let _kv_guard = registry.try_reserve_kv_blocks(&tenant, 5).unwrap();
+
+        let snapshot = registry.usage_snapshot(&tenant);
+        assert_eq!(snapshot.in_flight_requests, 1);
+        assert_eq!(snapshot.kv_blocks_in_use, 5);
+    }
+
+    #[test]
+    fn quota_update_takes_effect_immediately() {
+        let registry = QuotaRegistry::new(TenantQuota {
+            max_concurrent_requests: 1,
+            max_tokens_per_min: 1_000_000,
+            max_kv_blocks: 100,
+        });
+        let tenant = TenantId::from("acme-corp");
+
+        let _guard = registry.try_admit(&tenant, 10).unwrap();
+        assert!(matches!(
+            registry.try_admit(&tenant, 10),
+            Err(QuotaError::ConcurrencyLimitReached(_))
+        ));
+
+        registry.set_quota(
+            &tenant,
+            TenantQuota {
+                max_concurrent_requests: 2,
+                max_tokens_per_min: 1_000_000,
+                max_kv_blocks: 100,
+            },
+        );
+        assert!(registry.try_admit(&tenant, 10).is_ok());
+    }
+}
@@ -0,0 +1,40 @@
+//! Parallel tool-call detection across multiple sampled choices.
+//!
+//! With `n > 1` the frontend ends up running [`detect_and_parse_tool_calls`]
+//! once per choice on the hot path; for wide sampling that sequential loop
+//! dominates tail latency even though each choice is independent. This
+//! spreads the work across rayon's global pool instead.
+
+use crate::tool_call::{detect_and_parse_tool_calls, ToolCall, ToolCallFormat, ToolCallParseError};
+use rayon::prelude::*;
+
+/// Run [`detect_and_parse_tool_calls`] over every choice in `texts`
+/// concurrently, preserving input order in the returned `Vec`.
+pub fn detect_and_parse_tool_calls_batch(
+    texts: &[&str],
+    format: ToolCallFormat,
+) -> Vec<Result<Vec<ToolCall>, ToolCallParseError>> {
+    texts
+        .par_iter()
+        .map(|text| detect_and_parse_tool_calls(text, format))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_preserves_order() {
+        let texts = [
+            r#"<tool_call>{"name": "a", "arguments": {}}</tool_call>"#,
+            "no tool call here",
+            r#"<tool_call>{"name": "b", "arguments": {}}</tool_call>"#,
+        ];
+        let results = detect_and_parse_tool_calls_batch(&texts, ToolCallFormat::Hermes);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap()[0].name, "a");
+        assert!(results[1].is_err());
+        assert_eq!(results[2].as_ref().unwrap()[0].name, "b");
+    }
+}
@@ -0,0 +1,17 @@
+//! Model-specific tool-call parsing and prompt-formatting helpers.
+//!
+//! Each supported chat template (Hermes, Mistral, Llama, ...) gets its own
+//! parser function in [`tool_call`] that knows how to detect and decode a
+//! model's tool-call syntax, and its own renderer in [`tool_result`] that
+//! knows how to re-encode `role: tool` messages back into that same syntax
+//! for the next turn of the conversation.
+
+pub mod batch;
+pub mod structural_tag;
+pub mod tool_call;
+pub mod tool_result;
+
+pub use batch::detect_and_parse_tool_calls_batch;
+pub use structural_tag::{to_structural_tag, StructuralTag, ToolDefinition};
+pub use tool_call::{BuiltinTool, ToolCall, ToolCallFormat, ToolCallParseError, ToolInvocation};
+pub use tool_result::render_tool_result;
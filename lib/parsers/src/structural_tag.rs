@@ -0,0 +1,95 @@
+//! Conversion of tool definitions into the "structural tag" constraint
+//! representation consumed by constrained-decoding engines (vLLM, TRT-LLM).
+//!
+//! The parser crate already knows, per [`ToolCallFormat`], which literal
+//! strings wrap a tool call (`<tool_call>...</tool_call>`, `[TOOL_CALLS]
+//! [...]`, ...) and what JSON schema a call's arguments must satisfy. This
+//! module is the dual of that knowledge: instead of parsing text that
+//! already exists, it emits a constraint an engine can use to make sure the
+//! text it generates is parseable in the first place.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::tool_call::ToolCallFormat;
+
+/// Minimal tool definition needed to build a structural tag: a name and the
+/// JSON schema its arguments must conform to.
+#[derive(Debug, Clone)]
+pub struct ToolDefinition<'a> {
+    pub name: &'a str,
+    pub parameters_schema: &'a Value,
+}
+
+/// One `(begin, schema, end)` structure within the structural tag, matching
+/// the shape engines expect: a literal trigger string, the schema to
+/// constrain the enclosed JSON to, and a literal terminator.
+#[derive(Debug, Clone, Serialize)]
+pub struct Structure {
+    pub begin: String,
+    pub schema: Value,
+    pub end: String,
+}
+
+/// Top-level structural tag payload: the set of structures a decoder is
+/// allowed to emit, plus the literal strings that should trigger the
+/// constrained grammar in the first place.
+#[derive(Debug, Clone, Serialize)]
+pub struct StructuralTag {
+    #[serde(rename = "type")]
+    pub tag_type: &'static str,
+    pub structures: Vec<Structure>,
+    pub triggers: Vec<String>,
+}
+
+/// Build the structural-tag representation for a set of tools under the
+/// given chat template `format`, so the caller can hand this straight to an
+/// engine's `response_format` / guided-decoding field.
+pub fn to_structural_tag(tools: &[ToolDefinition<'_>], format: ToolCallFormat) -> StructuralTag {
+    let (open, close) = delimiters(format);
+
+    let structures = tools
+        .iter()
+        .map(|tool| Structure {
+            begin: format!("{open}{{\"name\": \"{}\", \"arguments\": ", tool.name),
+            schema: tool.parameters_schema.clone(),
+            end: format!("}}{close}"),
+        })
+        .collect();
+
+    StructuralTag {
+        tag_type: "structural_tag",
+        structures,
+        triggers: vec![open.to_string()],
+    }
+}
+
+/// The literal open/close markers a decoder must emit around a tool call
+/// for each format, mirroring [`crate::tool_call::detect_and_parse_tool_calls`].
+fn delimiters(format: ToolCallFormat) -> (&'static str, &'static str) {
+    match format {
+        ToolCallFormat::Hermes => ("<tool_call>", "</tool_call>"),
+        ToolCallFormat::Mistral => ("[TOOL_CALLS] [", "]"),
+        ToolCallFormat::Llama => ("", ""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn hermes_structure_wraps_schema_in_markers() {
+        let schema = json!({"type": "object", "properties": {"city": {"type": "string"}}});
+        let tool = ToolDefinition {
+            name: "get_weather",
+            parameters_schema: &schema,
+        };
+        let tag = to_structural_tag(&[tool], ToolCallFormat::Hermes);
+        assert_eq!(tag.tag_type, "structural_tag");
+        assert_eq!(tag.triggers, vec!["<tool_call>".to_string()]);
+        assert!(tag.structures[0].begin.starts_with("<tool_call>"));
+        assert!(tag.structures[0].end.ends_with("</tool_call>"));
+    }
+}
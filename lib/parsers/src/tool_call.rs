@@ -0,0 +1,229 @@
+//! Detection of tool/function calls embedded in raw model output.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Chat template family a completion was generated with. Parsing and
+/// rendering both key off this so the rest of the pipeline never has to
+/// know the per-model escape sequences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ToolCallFormat {
+    /// NousResearch Hermes-style `<tool_call>{...}</tool_call>` blocks.
+    Hermes,
+    /// Mistral's `[TOOL_CALLS] [...]` array syntax.
+    Mistral,
+    /// Llama 3.x JSON-in-prose / `<|python_tag|>` syntax.
+    Llama,
+}
+
+/// A single detected tool/function invocation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolCall {
+    /// Name of the function being invoked.
+    pub name: String,
+    /// Raw JSON arguments, as produced by the model.
+    pub arguments: serde_json::Value,
+}
+
+/// A model-native builtin tool, as opposed to a user-defined function.
+/// These have their own wire syntax per format and are routed to internal
+/// executors rather than the generic function-calling path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BuiltinTool {
+    /// Llama's `<|python_tag|>` inline code execution.
+    Python,
+    /// gpt-oss style `browser.search`/`browser.open` namespace calls.
+    Browser,
+    /// Generic `code_interpreter` namespace, shared by several formats.
+    CodeInterpreter,
+}
+
+/// Either a user-defined function call or a model-native builtin tool call.
+/// Keeping these as distinct variants lets callers route builtin tools to
+/// dedicated executors instead of forcing them through the generic
+/// function-call shape.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ToolInvocation {
+    Function(ToolCall),
+    Builtin {
+        tool: BuiltinTool,
+        /// Raw payload for the builtin call (code to run, query string,
+        /// etc.), still in the model's native encoding.
+        payload: String,
+    },
+}
+
+#[derive(Debug, Error)]
+pub enum ToolCallParseError {
+    #[error("no tool call markers found for format {0:?}")]
+    NotFound(ToolCallFormat),
+    #[error("malformed tool call payload: {0}")]
+    InvalidJson(#[from] serde_json::Error),
+}
+
+/// Scan `text` for tool calls encoded in the given `format`.
+///
+/// Returns [`ToolCallParseError::NotFound`] when no tool call for `format`
+/// is found in the text, and [`ToolCallParseError::InvalidJson`] when
+/// markers are present but the payload they wrap fails to parse.
+pub fn detect_and_parse_tool_calls(
+    text: &str,
+    format: ToolCallFormat,
+) -> Result<Vec<ToolCall>, ToolCallParseError> {
+    match format {
+        ToolCallFormat::Hermes => parse_hermes(text),
+        ToolCallFormat::Mistral => parse_mistral(text),
+        ToolCallFormat::Llama => parse_llama(text),
+    }
+}
+
+fn parse_hermes(text: &str) -> Result<Vec<ToolCall>, ToolCallParseError> {
+    const OPEN: &str = "<tool_call>";
+    const CLOSE: &str = "</tool_call>";
+
+    let mut calls = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(OPEN) {
+        let after_open = &rest[start + OPEN.len()..];
+        let Some(end) = after_open.find(CLOSE) else {
+            break;
+        };
+        let payload = after_open[..end].trim();
+        calls.push(parse_call_json(payload)?);
+        rest = &after_open[end + CLOSE.len()..];
+    }
+    if calls.is_empty() {
+        return Err(ToolCallParseError::NotFound(ToolCallFormat::Hermes));
+    }
+    Ok(calls)
+}
+
+fn parse_mistral(text: &str) -> Result<Vec<ToolCall>, ToolCallParseError> {
+    const MARKER: &str = "[TOOL_CALLS]";
+    let Some(start) = text.find(MARKER) else {
+        return Err(ToolCallParseError::NotFound(ToolCallFormat::Mistral));
+    };
+    let payload = text[start + MARKER.len()..].trim();
+    let raw: Vec<serde_json::Value> = serde_json::from_str(payload)?;
+    raw.into_iter().map(parse_call_value).collect()
+}
+
+fn parse_llama(text: &str) -> Result<Vec<ToolCall>, ToolCallParseError> {
+    // Llama 3 typically emits a single bare JSON object for its tool call,
+    // with no wrapping markers beyond the object itself.
+    let trimmed = text.trim();
+    if !trimmed.starts_with('{') {
+        return Err(ToolCallParseError::NotFound(ToolCallFormat::Llama));
+    }
+    Ok(vec![parse_call_json(trimmed)?])
+}
+
+/// Namespace prefixes that mark a function call as a builtin tool rather
+/// than a user-defined one, per format.
+fn builtin_namespace(name: &str) -> Option<BuiltinTool> {
+    match name {
+        "code_interpreter" => Some(BuiltinTool::CodeInterpreter),
+        n if n.starts_with("browser.") => Some(BuiltinTool::Browser),
+        n if n.starts_with("python") => Some(BuiltinTool::Python),
+        _ => None,
+    }
+}
+
+/// Scan `text` for either builtin-tool or generic function-call syntax,
+/// classifying each hit into the appropriate [`ToolInvocation`] variant.
+pub fn detect_and_parse_invocations(
+    text: &str,
+    format: ToolCallFormat,
+) -> Result<Vec<ToolInvocation>, ToolCallParseError> {
+    if format == ToolCallFormat::Llama {
+        if let Some(payload) = text.strip_prefix("<|python_tag|>") {
+            return Ok(vec![ToolInvocation::Builtin {
+                tool: BuiltinTool::Python,
+                payload: payload.trim().to_string(),
+            }]);
+        }
+    }
+
+    let calls = detect_and_parse_tool_calls(text, format)?;
+    Ok(calls
+        .into_iter()
+        .map(|call| match builtin_namespace(&call.name) {
+            Some(tool) => ToolInvocation::Builtin {
+                tool,
+                payload: call.arguments.to_string(),
+            },
+            None => ToolInvocation::Function(call),
+        })
+        .collect())
+}
+
+fn parse_call_json(payload: &str) -> Result<ToolCall, ToolCallParseError> {
+    let value: serde_json::Value = serde_json::from_str(payload)?;
+    parse_call_value(value)
+}
+
+fn parse_call_value(value: serde_json::Value) -> Result<ToolCall, ToolCallParseError> {
+    let name = value
+        .get("name")
+        .and_then(|n| n.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let arguments = value
+        .get("arguments")
+        .cloned()
+        .unwrap_or(serde_json::Value::Null);
+    Ok(ToolCall { name, arguments })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hermes_roundtrip() {
+        let text = r#"<tool_call>{"name": "get_weather", "arguments": {"city": "SF"}}</tool_call>"#;
+        let calls = detect_and_parse_tool_calls(text, ToolCallFormat::Hermes).unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].name, "get_weather");
+    }
+
+    #[test]
+    fn mistral_roundtrip() {
+        let text = r#"[TOOL_CALLS] [{"name": "get_weather", "arguments": {"city": "SF"}}]"#;
+        let calls = detect_and_parse_tool_calls(text, ToolCallFormat::Mistral).unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].name, "get_weather");
+    }
+
+    #[test]
+    fn llama_python_tag_is_builtin() {
+        let text = "<|python_tag|>print(1 + 1)";
+        let invocations = detect_and_parse_invocations(text, ToolCallFormat::Llama).unwrap();
+        assert_eq!(
+            invocations[0],
+            ToolInvocation::Builtin {
+                tool: BuiltinTool::Python,
+                payload: "print(1 + 1)".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn browser_namespace_is_builtin() {
+        let text = r#"<tool_call>{"name": "browser.search", "arguments": {"query": "rust"}}</tool_call>"#;
+        let invocations = detect_and_parse_invocations(text, ToolCallFormat::Hermes).unwrap();
+        assert!(matches!(
+            invocations[0],
+            ToolInvocation::Builtin {
+                tool: BuiltinTool::Browser,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn no_markers_is_not_found_error() {
+        let err = detect_and_parse_tool_calls("just plain text", ToolCallFormat::Hermes);
+        assert!(matches!(err, Err(ToolCallParseError::NotFound(_))));
+    }
+}
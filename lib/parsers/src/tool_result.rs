@@ -0,0 +1,94 @@
+//! Rendering `role: tool` messages back into a model's native prompt syntax.
+//!
+//! This is the inverse of [`crate::tool_call::detect_and_parse_tool_calls`]:
+//! once a tool has executed and produced a result, the preprocessing path
+//! needs to splice that result back into the next prompt using whatever
+//! escape sequence the target model's chat template expects, so deployments
+//! don't need a bespoke Jinja template per model just to handle multi-turn
+//! tool use.
+
+use crate::tool_call::ToolCallFormat;
+
+/// A tool result ready to be rendered, mirroring an OpenAI `role: tool`
+/// chat message.
+#[derive(Debug, Clone)]
+pub struct ToolResult<'a> {
+    /// `tool_call_id` this result answers, when the format tracks one.
+    pub tool_call_id: Option<&'a str>,
+    /// Name of the function that was called.
+    pub name: &'a str,
+    /// Raw content returned by the tool, already serialized to a string.
+    pub content: &'a str,
+}
+
+/// Render a single tool result into the prompt fragment `format` expects.
+pub fn render_tool_result(result: &ToolResult<'_>, format: ToolCallFormat) -> String {
+    match format {
+        ToolCallFormat::Hermes => render_hermes(result),
+        ToolCallFormat::Mistral => render_mistral(result),
+        ToolCallFormat::Llama => render_llama(result),
+    }
+}
+
+/// Render a batch of tool results in call order, one block per result,
+/// joined with a newline whatever the `format`.
+pub fn render_tool_results(results: &[ToolResult<'_>], format: ToolCallFormat) -> String {
+    results
+        .iter()
+        .map(|r| render_tool_result(r, format))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_hermes(result: &ToolResult<'_>) -> String {
+    format!(
+        "<tool_response>\n{{\"name\": \"{}\", \"content\": {}}}\n</tool_response>",
+        result.name,
+        serde_json::to_string(result.content).unwrap_or_default()
+    )
+}
+
+fn render_mistral(result: &ToolResult<'_>) -> String {
+    // Mistral ties results back to the call via tool_call_id rather than name.
+    let id = result.tool_call_id.unwrap_or_default();
+    format!(
+        "[TOOL_RESULTS] {{\"call_id\": \"{}\", \"content\": {}}}",
+        id,
+        serde_json::to_string(result.content).unwrap_or_default()
+    )
+}
+
+fn render_llama(result: &ToolResult<'_>) -> String {
+    format!(
+        "<|start_header_id|>ipython<|end_header_id|>\n\n{}<|eot_id|>",
+        result.content
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hermes_contains_name_and_content() {
+        let result = ToolResult {
+            tool_call_id: None,
+            name: "get_weather",
+            content: "{\"temp_f\": 72}",
+        };
+        let rendered = render_tool_result(&result, ToolCallFormat::Hermes);
+        assert!(rendered.contains("get_weather"));
+        assert!(rendered.starts_with("<tool_response>"));
+    }
+
+    #[test]
+    fn mistral_keys_on_call_id() {
+        let result = ToolResult {
+            tool_call_id: Some("call_123"),
+            name: "get_weather",
+            content: "{\"temp_f\": 72}",
+        };
+        let rendered = render_tool_result(&result, ToolCallFormat::Mistral);
+        assert!(rendered.contains("call_123"));
+    }
+}
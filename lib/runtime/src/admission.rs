@@ -0,0 +1,367 @@
+//! Admission control for ingress endpoints: a token-bucket rate limit
+//! plus a concurrency cap, tracked per `(model, api key)` pair so one
+//! noisy tenant can't starve another, with limits adjustable at runtime
+//! instead of requiring a restart. Rejections carry the retry delay a
+//! real frontend maps to an HTTP 429 with `Retry-After`.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+
+use crate::engine::{AsyncEngine, ResponseStream};
+use crate::error::RuntimeError;
+
+/// What a request needs to expose so [`AdmissionControlledEngine`] can
+/// look up the right limits for it.
+pub trait RateLimitKey {
+    fn model(&self) -> &str;
+    fn api_key(&self) -> &str;
+}
+
+/// Rate and concurrency limits for one `(model, api key)` pair.
+#[derive(Debug, Clone, Copy)]
+pub struct AdmissionLimits {
+    pub requests_per_second: f64,
+    pub burst: u32,
+    pub max_concurrent: u32,
+}
+
+impl Default for AdmissionLimits {
+    fn default() -> Self {
+        Self {
+            requests_per_second: 10.0,
+            burst: 10,
+            max_concurrent: 10,
+        }
+    }
+}
+
+/// Token bucket refilled lazily on each acquire attempt rather than on
+/// a background timer, so idle keys cost nothing between requests.
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(burst: u32) -> Self {
+        Self {
+            tokens: burst as f64,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Takes one token if available. On failure, returns how long until
+    /// the next token would be available.
+    fn try_acquire(&mut self, limits: &AdmissionLimits) -> Result<(), Duration> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * limits.requests_per_second).min(limits.burst as f64);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            let deficit = 1.0 - self.tokens;
+            let rate = limits.requests_per_second.max(f64::MIN_POSITIVE);
+            Err(Duration::from_secs_f64(deficit / rate))
+        }
+    }
+}
+
+struct KeyState {
+    limits: Mutex<AdmissionLimits>,
+    bucket: Mutex<TokenBucket>,
+    in_flight: AtomicI64,
+}
+
+impl KeyState {
+    fn new(limits: AdmissionLimits) -> Self {
+        Self {
+            bucket: Mutex::new(TokenBucket::new(limits.burst)),
+            limits: Mutex::new(limits),
+            in_flight: AtomicI64::new(0),
+        }
+    }
+}
+
+/// Releases the concurrency slot a successful admission took, once the
+/// guard drops at the end of the request.
+struct InFlightGuard {
+    state: Arc<KeyState>,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.state.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Per-key admission state, shared between [`AdmissionControlledEngine`]
+/// instances and whatever admin surface adjusts limits at runtime.
+#[derive(Default)]
+pub struct AdmissionControl {
+    keys: Mutex<HashMap<String, Arc<KeyState>>>,
+    default_limits: Mutex<AdmissionLimits>,
+}
+
+impl AdmissionControl {
+    pub fn new(default_limits: AdmissionLimits) -> Self {
+        Self {
+            keys: Mutex::new(HashMap::new()),
+            default_limits: Mutex::new(default_limits),
+        }
+    }
+
+    /// Requests currently admitted and in flight for `key`, not counting
+    /// whatever is still waiting on the rate limiter.
+    pub fn in_flight(&self, key: &str) -> i64 {
+        self.state(key).in_flight.load(Ordering::Relaxed)
+    }
+
+    fn state(&self, key: &str) -> Arc<KeyState> {
+        let mut keys = self.keys.lock().unwrap();
+        keys.entry(key.to_string())
+            .or_insert_with(|| Arc::new(KeyState::new(*self.default_limits.lock().unwrap())))
+            .clone()
+    }
+
+    /// Overrides the limits for one key immediately; already-admitted
+    /// requests are unaffected. A burst increase grants the extra
+    /// tokens right away rather than waiting for them to refill.
+    pub fn set_limits(&self, key: &str, limits: AdmissionLimits) {
+        let state = self.state(key);
+        let mut current = state.limits.lock().unwrap();
+        if limits.burst > current.burst {
+            let mut bucket = state.bucket.lock().unwrap();
+            bucket.tokens += (limits.burst - current.burst) as f64;
+        }
+        *current = limits;
+    }
+
+    fn try_admit(&self, key: &str) -> Result<InFlightGuard, RuntimeError> {
+        let state = self.state(key);
+        let limits = *state.limits.lock().unwrap();
+
+        let in_flight = state.in_flight.fetch_add(1, Ordering::Relaxed) + 1;
+        if in_flight > limits.max_concurrent as i64 {
+            state.in_flight.fetch_sub(1, Ordering::Relaxed);
+            return Err(RuntimeError::RateLimited(Duration::from_millis(100)));
+        }
+
+        if let Err(retry_after) = state.bucket.lock().unwrap().try_acquire(&limits) {
+            state.in_flight.fetch_sub(1, Ordering::Relaxed);
+            return Err(RuntimeError::RateLimited(retry_after));
+        }
+
+        Ok(InFlightGuard { state })
+    }
+}
+
+/// Wraps an `AsyncEngine` with [`AdmissionControl`], rejecting a request
+/// before it reaches `inner` once its `(model, api key)` pair is over
+/// its rate or concurrency limit.
+pub struct AdmissionControlledEngine<E> {
+    inner: E,
+    control: Arc<AdmissionControl>,
+}
+
+impl<E> AdmissionControlledEngine<E> {
+    pub fn new(inner: E, control: Arc<AdmissionControl>) -> Self {
+        Self { inner, control }
+    }
+}
+
+#[async_trait]
+impl<E, Req, Resp> AsyncEngine<Req, Resp> for AdmissionControlledEngine<E>
+where
+    E: AsyncEngine<Req, Resp>,
+    Req: RateLimitKey + Send + 'static,
+    Resp: Send + 'static,
+{
+    async fn generate(&self, request: Req) -> Result<ResponseStream<Resp>, RuntimeError> {
+        let key = format!("{}:{}", request.model(), request.api_key());
+        let guard = self.control.try_admit(&key)?;
+        let stream = self.inner.generate(request).await?;
+
+        // The guard is held in the unfold state for the life of the
+        // stream, not just until `generate` returns, so the concurrency
+        // slot isn't freed until the caller has actually finished
+        // reading the response — matching `drain::DrainableEngine` and
+        // `concurrency::ConcurrencyLimitedEngine`.
+        Ok(Box::pin(stream::unfold(
+            Some((stream, guard)),
+            |cursor| async move {
+                let (mut stream, guard) = cursor?;
+                stream
+                    .next()
+                    .await
+                    .map(|item| (item, Some((stream, guard))))
+            },
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::Notify;
+
+    struct FakeRequest {
+        model: &'static str,
+        api_key: &'static str,
+    }
+
+    impl RateLimitKey for FakeRequest {
+        fn model(&self) -> &str {
+            self.model
+        }
+
+        fn api_key(&self) -> &str {
+            self.api_key
+        }
+    }
+
+    struct FakeEngine;
+
+    #[async_trait]
+    impl AsyncEngine<FakeRequest, &'static str> for FakeEngine {
+        async fn generate(
+            &self,
+            _request: FakeRequest,
+        ) -> Result<ResponseStream<&'static str>, RuntimeError> {
+            Ok(Box::pin(stream::iter(vec![Ok("ok")])))
+        }
+    }
+
+    fn request() -> FakeRequest {
+        FakeRequest {
+            model: "llama",
+            api_key: "key-a",
+        }
+    }
+
+    #[tokio::test]
+    async fn admits_up_to_burst_then_rate_limits() {
+        let control = Arc::new(AdmissionControl::new(AdmissionLimits {
+            requests_per_second: 1.0,
+            burst: 2,
+            max_concurrent: 10,
+        }));
+        let engine = AdmissionControlledEngine::new(FakeEngine, control);
+
+        assert!(engine.generate(request()).await.is_ok());
+        assert!(engine.generate(request()).await.is_ok());
+        assert!(matches!(
+            engine.generate(request()).await,
+            Err(RuntimeError::RateLimited(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn different_keys_get_independent_buckets() {
+        let control = Arc::new(AdmissionControl::new(AdmissionLimits {
+            requests_per_second: 1.0,
+            burst: 1,
+            max_concurrent: 10,
+        }));
+        let engine = AdmissionControlledEngine::new(FakeEngine, control);
+
+        assert!(engine.generate(request()).await.is_ok());
+        assert!(engine
+            .generate(FakeRequest {
+                model: "llama",
+                api_key: "key-b",
+            })
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn runtime_limit_update_takes_effect_immediately() {
+        let control = Arc::new(AdmissionControl::new(AdmissionLimits {
+            requests_per_second: 1.0,
+            burst: 1,
+            max_concurrent: 10,
+        }));
+        let engine = AdmissionControlledEngine::new(FakeEngine, control.clone());
+
+        assert!(engine.generate(request()).await.is_ok());
+        assert!(matches!(
+            engine.generate(request()).await,
+            Err(RuntimeError::RateLimited(_))
+        ));
+
+        control.set_limits(
+            "llama:key-a",
+            AdmissionLimits {
+                requests_per_second: 1.0,
+                burst: 5,
+                max_concurrent: 10,
+            },
+        );
+        assert!(engine.generate(request()).await.is_ok());
+    }
+
+    struct SlowEngine {
+        release: Arc<Notify>,
+    }
+
+    #[async_trait]
+    impl AsyncEngine<FakeRequest, &'static str> for SlowEngine {
+        async fn generate(
+            &self,
+            _request: FakeRequest,
+        ) -> Result<ResponseStream<&'static str>, RuntimeError> {
+            let release = self.release.clone();
+            Ok(Box::pin(stream::unfold(Some(()), move |state| {
+                let release = release.clone();
+                async move {
+                    state?;
+                    release.notified().await;
+                    Some(("chunk", None))
+                }
+            }))
+            .map(Ok)
+            .boxed())
+        }
+    }
+
+    #[tokio::test]
+    async fn in_flight_count_stays_elevated_while_the_stream_is_still_being_read() {
+        let release = Arc::new(Notify::new());
+        let control = Arc::new(AdmissionControl::new(AdmissionLimits {
+            requests_per_second: 1.0,
+            burst: 1,
+            max_concurrent: 1,
+        }));
+        let engine = AdmissionControlledEngine::new(
+            SlowEngine {
+                release: release.clone(),
+            },
+            control.clone(),
+        );
+
+        let mut stream = engine.generate(request()).await.unwrap();
+
+        // The stream hasn't been polled to completion yet, so the slot
+        // it took must still be held — a second request for the same
+        // key should be rejected for being over the concurrency limit.
+        assert_eq!(control.in_flight("llama:key-a"), 1);
+        assert!(matches!(
+            engine.generate(request()).await,
+            Err(RuntimeError::RateLimited(_))
+        ));
+
+        release.notify_one();
+        stream.next().await.unwrap().unwrap();
+        drop(stream);
+
+        assert_eq!(control.in_flight("llama:key-a"), 0);
+    }
+}
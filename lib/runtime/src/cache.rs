@@ -0,0 +1,377 @@
+//! An optional response cache in front of engine dispatch: requests
+//! whose sampling settings make them deterministic (e.g.
+//! `temperature == 0`) are served from an exact match on their
+//! normalized cache key without reaching the engine at all, subject to
+//! a TTL and a bounded entry count. Every response is annotated with
+//! whether it came from the cache, so a frontend can surface that to
+//! callers or metrics.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+
+use crate::engine::{AsyncEngine, ResponseStream};
+use crate::error::RuntimeError;
+
+/// What a request needs to expose so [`CachingEngine`] can decide
+/// whether, and under what key, it's safe to cache.
+pub trait CacheKey {
+    /// The normalized key requests with the same semantic intent share
+    /// (canonicalized prompt, model, and sampling parameters).
+    fn cache_key(&self) -> String;
+    /// Whether this request's sampling settings make its output
+    /// deterministic, and therefore safe to serve verbatim from an
+    /// earlier exact-key match. Requests that return `false` are
+    /// always forwarded to the engine and never stored.
+    fn is_deterministic(&self) -> bool;
+}
+
+/// Whether a response was served from the cache or freshly generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheStatus {
+    Hit,
+    Miss,
+}
+
+/// A response annotated with where it came from.
+#[derive(Debug, Clone)]
+pub struct CacheAnnotated<Resp> {
+    pub response: Resp,
+    pub cache_status: CacheStatus,
+}
+
+/// Entry-count and freshness limits for a [`ResponseCache`].
+#[derive(Debug, Clone, Copy)]
+pub struct CacheConfig {
+    pub max_entries: usize,
+    pub ttl: Duration,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: 1024,
+            ttl: Duration::from_secs(60),
+        }
+    }
+}
+
+struct CacheEntry<Resp> {
+    chunks: Vec<Resp>,
+    inserted_at: Instant,
+}
+
+struct Inner<Resp> {
+    config: CacheConfig,
+    entries: Mutex<HashMap<String, CacheEntry<Resp>>>,
+    // Oldest-first insertion order, used for capacity eviction. Doesn't
+    // need to be exact LRU (accessed-but-not-reinserted entries aren't
+    // bumped) — good enough for a cache this size is meant to protect.
+    order: Mutex<VecDeque<String>>,
+}
+
+/// A complete, cached response (every chunk of its stream) keyed by
+/// [`CacheKey::cache_key`]. Cheap to clone — shares the same table.
+pub struct ResponseCache<Resp> {
+    inner: Arc<Inner<Resp>>,
+}
+
+impl<Resp> Clone for ResponseCache<Resp> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<Resp: Clone> ResponseCache<Resp> {
+    pub fn new(config: CacheConfig) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                config,
+                entries: Mutex::new(HashMap::new()),
+                order: Mutex::new(VecDeque::new()),
+            }),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get(&self, key: &str) -> Option<Vec<Resp>> {
+        let mut entries = self.inner.entries.lock().unwrap();
+        let entry = entries.get(key)?;
+        if entry.inserted_at.elapsed() > self.inner.config.ttl {
+            entries.remove(key);
+            return None;
+        }
+        Some(entry.chunks.clone())
+    }
+
+    fn put(&self, key: String, chunks: Vec<Resp>) {
+        let mut entries = self.inner.entries.lock().unwrap();
+        let mut order = self.inner.order.lock().unwrap();
+        if !entries.contains_key(&key) {
+            order.push_back(key.clone());
+        }
+        entries.insert(
+            key,
+            CacheEntry {
+                chunks,
+                inserted_at: Instant::now(),
+            },
+        );
+
+        while entries.len() > self.inner.config.max_entries {
+            match order.pop_front() {
+                Some(oldest) => {
+                    entries.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+/// Wraps an `AsyncEngine` with a [`ResponseCache`]: a deterministic
+/// request whose key is already cached is served straight from the
+/// cache, and a deterministic request that misses has its response
+/// collected and stored once its stream completes successfully. A
+/// non-deterministic request always passes through to `inner` and is
+/// never stored.
+pub struct CachingEngine<E, Resp> {
+    inner: E,
+    cache: ResponseCache<Resp>,
+}
+
+impl<E, Resp> CachingEngine<E, Resp> {
+    pub fn new(inner: E, cache: ResponseCache<Resp>) -> Self {
+        Self { inner, cache }
+    }
+}
+
+#[async_trait]
+impl<E, Req, Resp> AsyncEngine<Req, CacheAnnotated<Resp>> for CachingEngine<E, Resp>
+where
+    E: AsyncEngine<Req, Resp>,
+    Req: CacheKey + Send + 'static,
+    Resp: Clone + Send + 'static,
+{
+    async fn generate(
+        &self,
+        request: Req,
+    ) -> Result<ResponseStream<CacheAnnotated<Resp>>, RuntimeError> {
+        let deterministic = request.is_deterministic();
+        let key = request.cache_key();
+
+        if deterministic {
+            if let Some(chunks) = self.cache.get(&key) {
+                let hits = chunks.into_iter().map(|response| {
+                    Ok(CacheAnnotated {
+                        response,
+                        cache_status: CacheStatus::Hit,
+                    })
+                });
+                return Ok(Box::pin(stream::iter(hits)));
+            }
+        }
+
+        let stream = self.inner.generate(request).await?;
+        if !deterministic {
+            return Ok(Box::pin(stream.map(|item| {
+                item.map(|response| CacheAnnotated {
+                    response,
+                    cache_status: CacheStatus::Miss,
+                })
+            })));
+        }
+
+        let cache = self.cache.clone();
+        Ok(Box::pin(stream::unfold(
+            Some((stream, Vec::new(), key, cache)),
+            |state| async move {
+                let (mut stream, mut collected, key, cache) = state?;
+                match stream.next().await {
+                    Some(Ok(item)) => {
+                        collected.push(item.clone());
+                        let annotated = CacheAnnotated {
+                            response: item,
+                            cache_status: CacheStatus::Miss,
+                        };
+                        Some((Ok(annotated), Some((stream, collected, key, cache))))
+                    }
+                    // A stream that errors partway through is never
+                    // cached, so a retry after a transient failure
+                    // doesn't serve a truncated response forever.
+                    Some(Err(err)) => Some((Err(err), None)),
+                    None => {
+                        cache.put(key, collected);
+                        None
+                    }
+                }
+            },
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct FakeRequest {
+        key: &'static str,
+        deterministic: bool,
+    }
+
+    impl CacheKey for FakeRequest {
+        fn cache_key(&self) -> String {
+            self.key.to_string()
+        }
+
+        fn is_deterministic(&self) -> bool {
+            self.deterministic
+        }
+    }
+
+    struct CountingEngine {
+        calls: AtomicUsize,
+    }
+
+    impl CountingEngine {
+        fn new() -> Self {
+            Self {
+                calls: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl AsyncEngine<FakeRequest, String> for CountingEngine {
+        async fn generate(
+            &self,
+            request: FakeRequest,
+        ) -> Result<ResponseStream<String>, RuntimeError> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            Ok(Box::pin(stream::iter(vec![Ok(format!(
+                "response-for-{}",
+                request.key
+            ))])))
+        }
+    }
+
+    struct FailingEngine;
+
+    #[async_trait]
+    impl AsyncEngine<FakeRequest, String> for FailingEngine {
+        async fn generate(
+            &self,
+            _request: FakeRequest,
+        ) -> Result<ResponseStream<String>, RuntimeError> {
+            Ok(Box::pin(stream::iter(vec![Err(RuntimeError::Upstream(
+                "boom".to_string(),
+            ))])))
+        }
+    }
+
+    fn request(key: &'static str, deterministic: bool) -> FakeRequest {
+        FakeRequest { key, deterministic }
+    }
+
+    #[tokio::test]
+    async fn deterministic_repeat_request_is_served_from_cache() {
+        let engine = CountingEngine::new();
+        let caching = CachingEngine::new(engine, ResponseCache::new(CacheConfig::default()));
+
+        let mut first = caching.generate(request("a", true)).await.unwrap();
+        let first_item = first.next().await.unwrap().unwrap();
+        assert_eq!(first_item.cache_status, CacheStatus::Miss);
+        assert!(first.next().await.is_none());
+
+        let mut second = caching.generate(request("a", true)).await.unwrap();
+        let second_item = second.next().await.unwrap().unwrap();
+        assert_eq!(second_item.cache_status, CacheStatus::Hit);
+        assert_eq!(second_item.response, first_item.response);
+
+        assert_eq!(caching.inner.calls.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn non_deterministic_request_is_never_cached() {
+        let engine = CountingEngine::new();
+        let caching = CachingEngine::new(engine, ResponseCache::new(CacheConfig::default()));
+
+        for _ in 0..2 {
+            let mut stream = caching.generate(request("a", false)).await.unwrap();
+            let item = stream.next().await.unwrap().unwrap();
+            assert_eq!(item.cache_status, CacheStatus::Miss);
+        }
+        assert_eq!(caching.inner.calls.load(Ordering::Relaxed), 2);
+        assert!(caching.cache.is_empty());
+    }
+
+    #[tokio::test]
+    async fn expired_entry_is_treated_as_a_miss() {
+        let engine = CountingEngine::new();
+        let config = CacheConfig {
+            max_entries: 10,
+            ttl: Duration::from_millis(1),
+        };
+        let caching = CachingEngine::new(engine, ResponseCache::new(config));
+
+        let mut first = caching.generate(request("a", true)).await.unwrap();
+        first.next().await.unwrap().unwrap();
+        assert!(first.next().await.is_none());
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        let mut second = caching.generate(request("a", true)).await.unwrap();
+        let second_item = second.next().await.unwrap().unwrap();
+        assert_eq!(second_item.cache_status, CacheStatus::Miss);
+        assert_eq!(caching.inner.calls.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn capacity_limit_evicts_the_oldest_entry() {
+        let engine = CountingEngine::new();
+        let config = CacheConfig {
+            max_entries: 1,
+            ttl: Duration::from_secs(60),
+        };
+        let caching = CachingEngine::new(engine, ResponseCache::new(config));
+
+        let mut stream_a = caching.generate(request("a", true)).await.unwrap();
+        while stream_a.next().await.is_some() {}
+        let mut stream_b = caching.generate(request("b", true)).await.unwrap();
+        while stream_b.next().await.is_some() {}
+
+        let mut replay_a = caching.generate(request("a", true)).await.unwrap();
+        assert_eq!(
+            replay_a.next().await.unwrap().unwrap().cache_status,
+            CacheStatus::Miss
+        );
+
+        let mut replay_b = caching.generate(request("b", true)).await.unwrap();
+        assert_eq!(
+            replay_b.next().await.unwrap().unwrap().cache_status,
+            CacheStatus::Hit
+        );
+    }
+
+    #[tokio::test]
+    async fn a_stream_that_errors_is_not_cached() {
+        let caching = CachingEngine::new(FailingEngine, ResponseCache::new(CacheConfig::default()));
+
+        let mut stream = caching.generate(request("a", true)).await.unwrap();
+        assert!(matches!(stream.next().await, Some(Err(_))));
+        assert!(caching.cache.is_empty());
+    }
+}
@@ -0,0 +1,213 @@
+//! Remote cancellation: when a client disconnects from the HTTP
+//! frontend mid-stream, `context.stop_generating()` needs to reach the
+//! worker that's actually producing tokens, not just tear down the
+//! frontend's local connection. A [`CancellationRegistry`] is the
+//! control-plane side of that: a control message carrying a request id
+//! (typically relayed over the same message bus as the request itself)
+//! looks up the in-flight [`CancellationHandle`] for that id and wakes
+//! it, which [`CancellableEngine`] turns into early stream termination
+//! at the worker.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+use tokio::sync::Notify;
+
+use crate::engine::{AsyncEngine, ResponseStream};
+use crate::error::RuntimeError;
+
+/// What a request needs to expose so it can be looked up by a
+/// cancellation control message.
+pub trait HasRequestId {
+    fn request_id(&self) -> &str;
+}
+
+struct CancelState {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+/// Tracks in-flight requests by id so a cancellation control message
+/// can reach the handle a worker is waiting on. Cheap to clone — shares
+/// the same table, the way [`crate::metrics::MetricsRegistry`] is
+/// shared across the engines it instruments.
+#[derive(Clone, Default)]
+pub struct CancellationRegistry {
+    entries: Arc<Mutex<HashMap<String, Arc<CancelState>>>>,
+}
+
+impl CancellationRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `request_id` as in flight and returns the handle a
+    /// worker watches for cancellation. Deregistered automatically when
+    /// the handle is dropped.
+    pub fn register(&self, request_id: &str) -> CancellationHandle {
+        let state = Arc::new(CancelState {
+            cancelled: AtomicBool::new(false),
+            notify: Notify::new(),
+        });
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(request_id.to_string(), state.clone());
+        CancellationHandle {
+            request_id: request_id.to_string(),
+            state,
+            entries: self.entries.clone(),
+        }
+    }
+
+    /// Applies a `stop_generating` control message for `request_id`. A
+    /// no-op if the request already finished or was never registered
+    /// here (e.g. it landed on a different worker).
+    pub fn cancel(&self, request_id: &str) {
+        if let Some(state) = self.entries.lock().unwrap().get(request_id) {
+            state.cancelled.store(true, Ordering::Release);
+            state.notify.notify_one();
+        }
+    }
+}
+
+/// A request's registration in a [`CancellationRegistry`]. Dropping it
+/// (normally because its stream ran to completion) removes the entry so
+/// a late-arriving cancellation for the same id is a harmless no-op.
+pub struct CancellationHandle {
+    request_id: String,
+    state: Arc<CancelState>,
+    entries: Arc<Mutex<HashMap<String, Arc<CancelState>>>>,
+}
+
+impl CancellationHandle {
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Resolves once [`CancellationRegistry::cancel`] is called for this
+    /// handle's request id, or immediately if it already was.
+    pub async fn cancelled(&self) {
+        if self.is_cancelled() {
+            return;
+        }
+        self.state.notify.notified().await;
+    }
+}
+
+impl Drop for CancellationHandle {
+    fn drop(&mut self) {
+        self.entries.lock().unwrap().remove(&self.request_id);
+    }
+}
+
+/// Splices cancellation into a response stream: once `handle` is
+/// cancelled, the stream yields one final [`RuntimeError::Cancelled`]
+/// and ends, regardless of whether `inner` had more to say.
+fn cancellable<Resp: Send + 'static>(
+    inner: ResponseStream<Resp>,
+    handle: CancellationHandle,
+) -> ResponseStream<Resp> {
+    Box::pin(stream::unfold(Some((inner, handle)), |state| async move {
+        let (mut inner, handle) = state?;
+        tokio::select! {
+            _ = handle.cancelled() => Some((Err(RuntimeError::Cancelled), None)),
+            item = inner.next() => item.map(|item| (item, Some((inner, handle)))),
+        }
+    }))
+}
+
+/// Wraps an `AsyncEngine` so every request is registered with a
+/// [`CancellationRegistry`] for the duration of its stream, letting a
+/// remote `stop_generating()` control message end it early.
+pub struct CancellableEngine<E> {
+    inner: E,
+    registry: CancellationRegistry,
+}
+
+impl<E> CancellableEngine<E> {
+    pub fn new(inner: E, registry: CancellationRegistry) -> Self {
+        Self { inner, registry }
+    }
+}
+
+#[async_trait]
+impl<E, Req, Resp> AsyncEngine<Req, Resp> for CancellableEngine<E>
+where
+    E: AsyncEngine<Req, Resp>,
+    Req: HasRequestId + Send + 'static,
+    Resp: Send + 'static,
+{
+    async fn generate(&self, request: Req) -> Result<ResponseStream<Resp>, RuntimeError> {
+        let handle = self.registry.register(request.request_id());
+        let stream = self.inner.generate(request).await?;
+        Ok(cancellable(stream, handle))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeRequest {
+        id: &'static str,
+    }
+
+    impl HasRequestId for FakeRequest {
+        fn request_id(&self) -> &str {
+            self.id
+        }
+    }
+
+    struct CountingEngine;
+
+    #[async_trait]
+    impl AsyncEngine<FakeRequest, u32> for CountingEngine {
+        async fn generate(
+            &self,
+            _request: FakeRequest,
+        ) -> Result<ResponseStream<u32>, RuntimeError> {
+            Ok(Box::pin(stream::iter((0..).map(Ok))))
+        }
+    }
+
+    #[tokio::test]
+    async fn cancelling_by_request_id_ends_the_stream() {
+        let registry = CancellationRegistry::new();
+        let engine = CancellableEngine::new(CountingEngine, registry.clone());
+
+        let mut stream = engine.generate(FakeRequest { id: "req-1" }).await.unwrap();
+        assert_eq!(stream.next().await.unwrap().unwrap(), 0);
+
+        registry.cancel("req-1");
+
+        let mut saw_cancelled = false;
+        while let Some(item) = stream.next().await {
+            if matches!(item, Err(RuntimeError::Cancelled)) {
+                saw_cancelled = true;
+                break;
+            }
+        }
+        assert!(saw_cancelled);
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn cancelling_an_unknown_request_id_is_a_no_op() {
+        let registry = CancellationRegistry::new();
+        registry.cancel("does-not-exist");
+    }
+
+    #[tokio::test]
+    async fn completed_request_is_deregistered() {
+        let registry = CancellationRegistry::new();
+        {
+            let _handle = registry.register("req-1");
+            assert_eq!(registry.entries.lock().unwrap().len(), 1);
+        }
+        assert_eq!(registry.entries.lock().unwrap().len(), 0);
+    }
+}
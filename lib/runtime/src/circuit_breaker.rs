@@ -0,0 +1,411 @@
+//! Circuit breaker for egress clients: tracks failure rate per
+//! downstream instance and stops sending it traffic once that rate
+//! crosses a threshold, instead of letting every caller wait out a full
+//! timeout against a worker that's already gone. Standard
+//! closed/open/half-open state machine, with a configurable number of
+//! half-open probes before deciding whether to close or reopen.
+
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+
+use crate::engine::{AsyncEngine, ResponseStream};
+use crate::error::RuntimeError;
+
+/// When the circuit is open, [`CircuitBreaker`]'s `generate` fails fast
+/// with a [`RuntimeError::Upstream`] carrying this error's message
+/// instead of calling the wrapped engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitOpenError;
+
+impl std::fmt::Display for CircuitOpenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "circuit breaker is open")
+    }
+}
+
+/// Tuning knobs for [`CircuitBreaker`].
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    /// Minimum number of calls counted since the circuit last closed
+    /// before the failure rate is trusted enough to trip the circuit.
+    /// The counts are cumulative, not a rolling window: they reset only
+    /// when the circuit closes.
+    pub min_calls: u32,
+    /// Failure rate (0.0-1.0) over those counted calls that trips the
+    /// circuit from closed to open.
+    pub failure_threshold: f64,
+    /// How long the circuit stays open before allowing a half-open
+    /// probe.
+    pub open_duration: Duration,
+    /// Consecutive successful half-open probes required to close the
+    /// circuit again. A single failed probe reopens it immediately.
+    pub half_open_successes: u32,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            min_calls: 10,
+            failure_threshold: 0.5,
+            open_duration: Duration::from_secs(30),
+            half_open_successes: 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+struct Window {
+    calls: u32,
+    failures: u32,
+}
+
+/// Per-downstream-instance state: the call and failure counts since the
+/// circuit last closed, plus whatever's needed to drive the
+/// open/half-open transitions.
+struct BreakerState {
+    state: State,
+    window: Window,
+    opened_at: Option<Instant>,
+    half_open_successes: u32,
+}
+
+/// The breaker's shared bookkeeping, held behind an `Arc` so it can be
+/// moved into the response stream returned by `generate` and updated
+/// once that stream reaches its real, terminal outcome rather than when
+/// `generate` merely returns a stream handle.
+struct CircuitState {
+    config: CircuitBreakerConfig,
+    state: Mutex<BreakerState>,
+    trips_total: AtomicU64,
+    half_open_probes_in_flight: AtomicU32,
+}
+
+impl CircuitState {
+    /// Decides whether a call is allowed to proceed right now, moving
+    /// open -> half-open once `open_duration` has elapsed. Returns
+    /// `true` if this call is the one half-open probe in flight.
+    fn admit(&self) -> Result<bool, CircuitOpenError> {
+        let mut state = self.state.lock().unwrap();
+        match state.state {
+            State::Closed => Ok(false),
+            State::HalfOpen => {
+                if self.half_open_probes_in_flight.load(Ordering::Relaxed) > 0 {
+                    Err(CircuitOpenError)
+                } else {
+                    self.half_open_probes_in_flight
+                        .fetch_add(1, Ordering::Relaxed);
+                    Ok(true)
+                }
+            }
+            State::Open => {
+                let elapsed = state.opened_at.map(|at| at.elapsed()).unwrap_or_default();
+                if elapsed >= self.config.open_duration {
+                    state.state = State::HalfOpen;
+                    state.half_open_successes = 0;
+                    self.half_open_probes_in_flight
+                        .fetch_add(1, Ordering::Relaxed);
+                    Ok(true)
+                } else {
+                    Err(CircuitOpenError)
+                }
+            }
+        }
+    }
+
+    fn record(&self, is_probe: bool, succeeded: bool) {
+        if is_probe {
+            self.half_open_probes_in_flight
+                .fetch_sub(1, Ordering::Relaxed);
+        }
+
+        let mut state = self.state.lock().unwrap();
+        match state.state {
+            State::HalfOpen => {
+                if succeeded {
+                    state.half_open_successes += 1;
+                    if state.half_open_successes >= self.config.half_open_successes {
+                        state.state = State::Closed;
+                        state.window = Window {
+                            calls: 0,
+                            failures: 0,
+                        };
+                    }
+                } else {
+                    self.trip(&mut state);
+                }
+            }
+            State::Closed => {
+                state.window.calls += 1;
+                if !succeeded {
+                    state.window.failures += 1;
+                }
+                let rate = state.window.failures as f64 / state.window.calls as f64;
+                if state.window.calls >= self.config.min_calls
+                    && rate >= self.config.failure_threshold
+                {
+                    self.trip(&mut state);
+                }
+            }
+            State::Open => {}
+        }
+    }
+
+    fn trip(&self, state: &mut BreakerState) {
+        state.state = State::Open;
+        state.opened_at = Some(Instant::now());
+        self.trips_total.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Wraps any `AsyncEngine` with a circuit breaker, so a crashed or
+/// overloaded downstream stops receiving traffic immediately instead of
+/// every caller timing out against it one request at a time.
+pub struct CircuitBreaker<E> {
+    inner: E,
+    state: Arc<CircuitState>,
+}
+
+impl<E> CircuitBreaker<E> {
+    pub fn new(inner: E, config: CircuitBreakerConfig) -> Self {
+        Self {
+            inner,
+            state: Arc::new(CircuitState {
+                config,
+                state: Mutex::new(BreakerState {
+                    state: State::Closed,
+                    window: Window {
+                        calls: 0,
+                        failures: 0,
+                    },
+                    opened_at: None,
+                    half_open_successes: 0,
+                }),
+                trips_total: AtomicU64::new(0),
+                half_open_probes_in_flight: AtomicU32::new(0),
+            }),
+        }
+    }
+
+    /// Total number of times the circuit has tripped from closed to
+    /// open, for exposing on a status endpoint.
+    pub fn trips_total(&self) -> u64 {
+        self.state.trips_total.load(Ordering::Relaxed)
+    }
+}
+
+#[async_trait]
+impl<E, Req, Resp> AsyncEngine<Req, Resp> for CircuitBreaker<E>
+where
+    E: AsyncEngine<Req, Resp>,
+    Req: Send + 'static,
+    Resp: Send + 'static,
+{
+    async fn generate(&self, request: Req) -> Result<ResponseStream<Resp>, RuntimeError> {
+        let is_probe = self
+            .state
+            .admit()
+            .map_err(|_| RuntimeError::Upstream(CircuitOpenError.to_string()))?;
+
+        match self.inner.generate(request).await {
+            Ok(stream) => {
+                let breaker_state = self.state.clone();
+                // Whether the call counts as a success is only known once
+                // the stream reaches its real terminal outcome, not once
+                // `generate` hands back a stream handle — a downstream
+                // that accepts the connection and then errors on every
+                // chunk must still trip the breaker, matching
+                // `admission::AdmissionControlledEngine` and
+                // `priority::PriorityQueueEngine`.
+                Ok(Box::pin(stream::unfold(
+                    Some((stream, breaker_state, is_probe, false)),
+                    |cursor| async move {
+                        let (mut stream, breaker_state, is_probe, saw_error) = cursor?;
+                        match stream.next().await {
+                            Some(item) => {
+                                let saw_error = saw_error || item.is_err();
+                                Some((item, Some((stream, breaker_state, is_probe, saw_error))))
+                            }
+                            None => {
+                                breaker_state.record(is_probe, !saw_error);
+                                None
+                            }
+                        }
+                    },
+                )))
+            }
+            Err(e) => {
+                self.state.record(is_probe, false);
+                Err(e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicBool;
+    use tokio::sync::Notify;
+
+    struct FlakyEngine {
+        fail: AtomicBool,
+    }
+
+    #[async_trait]
+    impl AsyncEngine<(), &'static str> for FlakyEngine {
+        async fn generate(
+            &self,
+            _request: (),
+        ) -> Result<ResponseStream<&'static str>, RuntimeError> {
+            if self.fail.load(Ordering::Relaxed) {
+                Err(RuntimeError::Upstream("down".to_string()))
+            } else {
+                Ok(Box::pin(stream::iter(vec![Ok("ok")])))
+            }
+        }
+    }
+
+    fn config() -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            min_calls: 2,
+            failure_threshold: 0.5,
+            open_duration: Duration::from_millis(10),
+            half_open_successes: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn trips_open_after_failure_threshold() {
+        let engine = FlakyEngine {
+            fail: AtomicBool::new(true),
+        };
+        let breaker = CircuitBreaker::new(engine, config());
+
+        for _ in 0..2 {
+            assert!(breaker.generate(()).await.is_err());
+        }
+        assert_eq!(breaker.trips_total(), 1);
+
+        // Circuit is open: fails fast without calling the inner engine.
+        assert!(matches!(
+            breaker.generate(()).await,
+            Err(RuntimeError::Upstream(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn half_open_probe_closes_circuit_on_success() {
+        let engine = FlakyEngine {
+            fail: AtomicBool::new(true),
+        };
+        let breaker = CircuitBreaker::new(engine, config());
+
+        for _ in 0..2 {
+            let _ = breaker.generate(()).await;
+        }
+        assert_eq!(breaker.trips_total(), 1);
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        breaker.inner.fail.store(false, Ordering::Relaxed);
+
+        let mut probe = breaker.generate(()).await.unwrap();
+        // Drain the probe's stream so its outcome is actually recorded —
+        // the circuit only leaves half-open once this resolves, not once
+        // the stream handle comes back.
+        while probe.next().await.is_some() {}
+
+        assert!(breaker.generate(()).await.is_ok());
+    }
+
+    /// An engine that always hands back a stream (i.e. `generate` itself
+    /// never errors), but whose stream's only chunk is an `Err` — the
+    /// "worker accepted the connection and then errors on every chunk"
+    /// case the circuit breaker exists to catch.
+    struct ConnectsThenFailsEngine;
+
+    #[async_trait]
+    impl AsyncEngine<(), &'static str> for ConnectsThenFailsEngine {
+        async fn generate(
+            &self,
+            _request: (),
+        ) -> Result<ResponseStream<&'static str>, RuntimeError> {
+            Ok(Box::pin(stream::iter(vec![Err(RuntimeError::Upstream(
+                "down".to_string(),
+            ))])))
+        }
+    }
+
+    #[tokio::test]
+    async fn stream_errors_after_a_successful_handle_still_trip_the_circuit() {
+        let breaker = CircuitBreaker::new(ConnectsThenFailsEngine, config());
+
+        for _ in 0..2 {
+            let mut stream = breaker.generate(()).await.unwrap();
+            assert!(stream.next().await.unwrap().is_err());
+            // Drain to the terminal `None` so the failure is actually
+            // recorded, not just observed in the one `Err` chunk.
+            assert!(stream.next().await.is_none());
+        }
+
+        assert_eq!(breaker.trips_total(), 1);
+        assert!(matches!(
+            breaker.generate(()).await,
+            Err(RuntimeError::Upstream(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn success_is_not_recorded_until_the_stream_is_drained() {
+        let release = Arc::new(Notify::new());
+
+        struct SlowEngine {
+            release: Arc<Notify>,
+        }
+
+        #[async_trait]
+        impl AsyncEngine<(), &'static str> for SlowEngine {
+            async fn generate(
+                &self,
+                _request: (),
+            ) -> Result<ResponseStream<&'static str>, RuntimeError> {
+                let release = self.release.clone();
+                Ok(Box::pin(stream::unfold(Some(()), move |state| {
+                    let release = release.clone();
+                    async move {
+                        state?;
+                        release.notified().await;
+                        Some((Ok("chunk"), None))
+                    }
+                })))
+            }
+        }
+
+        let breaker = CircuitBreaker::new(
+            SlowEngine {
+                release: release.clone(),
+            },
+            config(),
+        );
+
+        let mut stream = breaker.generate(()).await.unwrap();
+        // The stream hasn't resolved yet, so nothing should have been
+        // recorded against the window.
+        assert_eq!(breaker.trips_total(), 0);
+        assert_eq!(breaker.state.state.lock().unwrap().window.calls, 0);
+
+        release.notify_one();
+        assert!(stream.next().await.unwrap().is_ok());
+        assert!(stream.next().await.is_none());
+        assert_eq!(breaker.state.state.lock().unwrap().window.calls, 1);
+    }
+}
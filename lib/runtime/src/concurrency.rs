@@ -0,0 +1,264 @@
+//! Per-endpoint concurrency limiting: each endpoint gets its own
+//! semaphore-backed slot count, and a request that arrives once every
+//! slot is taken waits in a bounded queue rather than either blocking
+//! the caller forever or being admitted into an already-overloaded
+//! worker. Once the queue itself is full, requests are shed immediately
+//! with [`RuntimeError::RateLimited`] instead of timing out
+//! unpredictably deep in the pipeline. Queue depth and wait time are
+//! reported through the same [`crate::metrics::EndpointMetrics`] every
+//! other pipeline stage uses.
+
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::engine::{AsyncEngine, ResponseStream};
+use crate::error::RuntimeError;
+use crate::metrics::EndpointMetrics;
+
+/// Concurrency and queueing limits for one endpoint.
+#[derive(Debug, Clone, Copy)]
+pub struct ConcurrencyLimits {
+    pub max_concurrent: usize,
+    pub max_queue_depth: usize,
+}
+
+struct Inner {
+    semaphore: Arc<Semaphore>,
+    limits: ConcurrencyLimits,
+    queued: AtomicI64,
+    metrics: Arc<EndpointMetrics>,
+}
+
+/// Shared concurrency-limiting state for one endpoint. Clone to hand
+/// the same limiter to every [`ConcurrencyLimitedEngine`] fronting that
+/// endpoint.
+#[derive(Clone)]
+pub struct ConcurrencyLimiter {
+    inner: Arc<Inner>,
+}
+
+impl ConcurrencyLimiter {
+    pub fn new(limits: ConcurrencyLimits, metrics: Arc<EndpointMetrics>) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                semaphore: Arc::new(Semaphore::new(limits.max_concurrent)),
+                limits,
+                queued: AtomicI64::new(0),
+                metrics,
+            }),
+        }
+    }
+
+    /// Requests waiting for a slot right now, not counting whatever is
+    /// already admitted and in flight.
+    pub fn queue_depth(&self) -> i64 {
+        self.inner.queued.load(Ordering::Relaxed)
+    }
+
+    /// Waits for a concurrency slot, rejecting immediately if the
+    /// bounded wait queue is already full.
+    async fn admit(&self) -> Result<OwnedSemaphorePermit, RuntimeError> {
+        if self.inner.semaphore.available_permits() == 0
+            && self.inner.queued.load(Ordering::Relaxed) as usize
+                >= self.inner.limits.max_queue_depth
+        {
+            return Err(RuntimeError::RateLimited(Duration::from_millis(100)));
+        }
+
+        self.inner.queued.fetch_add(1, Ordering::Relaxed);
+        self.inner.metrics.enqueued();
+        let started_waiting = Instant::now();
+
+        let permit = self
+            .inner
+            .semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("endpoint semaphore is never closed");
+
+        self.inner.queued.fetch_sub(1, Ordering::Relaxed);
+        self.inner
+            .metrics
+            .dequeued(started_waiting.elapsed().as_secs_f64() * 1000.0);
+        Ok(permit)
+    }
+}
+
+/// Wraps an `AsyncEngine` with a [`ConcurrencyLimiter`]: a request over
+/// the concurrency limit waits for a slot (queueing and wait time are
+/// reported to `metrics`), and a request that arrives once the queue is
+/// also full is rejected outright.
+pub struct ConcurrencyLimitedEngine<E> {
+    inner: E,
+    limiter: ConcurrencyLimiter,
+}
+
+impl<E> ConcurrencyLimitedEngine<E> {
+    pub fn new(inner: E, limiter: ConcurrencyLimiter) -> Self {
+        Self { inner, limiter }
+    }
+}
+
+#[async_trait]
+impl<E, Req, Resp> AsyncEngine<Req, Resp> for ConcurrencyLimitedEngine<E>
+where
+    E: AsyncEngine<Req, Resp>,
+    Req: Send + 'static,
+    Resp: Send + 'static,
+{
+    async fn generate(&self, request: Req) -> Result<ResponseStream<Resp>, RuntimeError> {
+        let permit = self.limiter.admit().await?;
+        let stream = self.inner.generate(request).await?;
+
+        // The permit is held in the unfold state for the life of the
+        // stream, not just until `generate` returns, so the slot isn't
+        // freed until the caller has actually finished reading the
+        // response — matching how `drain::DrainableEngine` holds its
+        // guard across the whole stream.
+        Ok(Box::pin(stream::unfold(
+            Some((stream, permit)),
+            |cursor| async move {
+                let (mut stream, permit) = cursor?;
+                stream
+                    .next()
+                    .await
+                    .map(|item| (item, Some((stream, permit))))
+            },
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::Notify;
+
+    struct SlowEngine {
+        release: Arc<Notify>,
+    }
+
+    #[async_trait]
+    impl AsyncEngine<(), &'static str> for SlowEngine {
+        async fn generate(
+            &self,
+            _request: (),
+        ) -> Result<ResponseStream<&'static str>, RuntimeError> {
+            let release = self.release.clone();
+            Ok(Box::pin(stream::unfold(Some(()), move |state| {
+                let release = release.clone();
+                async move {
+                    let _ = state?;
+                    release.notified().await;
+                    Some(("chunk", None))
+                }
+            }))
+            .map(Ok)
+            .boxed())
+        }
+    }
+
+    fn metrics() -> Arc<EndpointMetrics> {
+        Arc::new(EndpointMetrics::default())
+    }
+
+    #[tokio::test]
+    async fn a_request_past_the_concurrency_limit_queues_instead_of_failing() {
+        let release = Arc::new(Notify::new());
+        let limiter = ConcurrencyLimiter::new(
+            ConcurrencyLimits {
+                max_concurrent: 1,
+                max_queue_depth: 1,
+            },
+            metrics(),
+        );
+        let engine = ConcurrencyLimitedEngine::new(
+            SlowEngine {
+                release: release.clone(),
+            },
+            limiter.clone(),
+        );
+
+        let mut first = engine.generate(()).await.unwrap();
+
+        // First request holds the only slot; queue the second behind it.
+        let second = tokio::spawn(async move { engine.generate(()).await });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(limiter.queue_depth(), 1);
+
+        release.notify_one();
+        first.next().await.unwrap().unwrap();
+        // Releases the first request's permit so the queued second one
+        // can be admitted.
+        drop(first);
+
+        let mut second_stream = second.await.unwrap().unwrap();
+        release.notify_one();
+        second_stream.next().await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn a_full_queue_rejects_new_requests_immediately() {
+        let release = Arc::new(Notify::new());
+        let limiter = ConcurrencyLimiter::new(
+            ConcurrencyLimits {
+                max_concurrent: 1,
+                max_queue_depth: 1,
+            },
+            metrics(),
+        );
+        let engine = Arc::new(ConcurrencyLimitedEngine::new(
+            SlowEngine {
+                release: release.clone(),
+            },
+            limiter.clone(),
+        ));
+
+        let _first = engine.generate(()).await.unwrap();
+        let engine_clone = engine.clone();
+        let _second = tokio::spawn(async move { engine_clone.generate(()).await });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        assert!(matches!(
+            engine.generate(()).await,
+            Err(RuntimeError::RateLimited(_))
+        ));
+
+        release.notify_waiters();
+    }
+
+    #[tokio::test]
+    async fn queue_depth_and_wait_time_are_reported_to_metrics() {
+        let release = Arc::new(Notify::new());
+        let endpoint_metrics = metrics();
+        let limiter = ConcurrencyLimiter::new(
+            ConcurrencyLimits {
+                max_concurrent: 1,
+                max_queue_depth: 1,
+            },
+            endpoint_metrics.clone(),
+        );
+        let engine = ConcurrencyLimitedEngine::new(
+            SlowEngine {
+                release: release.clone(),
+            },
+            limiter.clone(),
+        );
+
+        let mut first = engine.generate(()).await.unwrap();
+        let waiter = tokio::spawn(async move { engine.generate(()).await });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(endpoint_metrics.queue_depth(), 1);
+
+        release.notify_one();
+        first.next().await.unwrap().unwrap();
+        drop(first);
+        let _ = waiter.await.unwrap().unwrap();
+        assert_eq!(endpoint_metrics.queue_depth(), 0);
+    }
+}
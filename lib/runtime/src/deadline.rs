@@ -0,0 +1,177 @@
+//! Deadline propagation across pipeline hops: a request carries a
+//! budget set once at the frontend, and every hop after that —
+//! including across a network call — sees how much of it is left
+//! rather than re-deriving its own timeout. A worker that receives a
+//! request whose deadline has already passed can skip the work
+//! entirely instead of doing it for an answer nobody is waiting for
+//! anymore, and a scheduler can use [`Deadline::remaining`] to order
+//! requests by how little budget they have left.
+
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+use crate::engine::{AsyncEngine, ResponseStream};
+use crate::error::RuntimeError;
+
+/// How much budget is left for a request before its caller gives up on
+/// it, anchored to a local [`Instant`] so comparisons never drift with
+/// wall-clock adjustments. `Instant` itself isn't meaningful across a
+/// process boundary, so wire transport goes through [`Deadline::encode`]
+/// / [`Deadline::decode`], which carry the remaining duration instead
+/// of the absolute instant.
+#[derive(Debug, Clone, Copy)]
+pub struct Deadline {
+    expires_at: Instant,
+}
+
+impl Deadline {
+    /// A deadline `timeout` from now.
+    pub fn after(timeout: Duration) -> Self {
+        Self {
+            expires_at: Instant::now() + timeout,
+        }
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.expires_at.saturating_duration_since(Instant::now())
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.remaining() == Duration::ZERO
+    }
+
+    /// Encodes the remaining budget for a header or wire field, e.g.
+    /// `x-dynamo-deadline-ms`. Re-anchored to the receiving hop's clock
+    /// on [`Deadline::decode`], so network latency between hops is
+    /// implicitly charged against the budget.
+    pub fn encode(&self) -> String {
+        self.remaining().as_millis().to_string()
+    }
+
+    pub fn decode(value: &str) -> Option<Self> {
+        let millis: u64 = value.parse().ok()?;
+        Some(Self::after(Duration::from_millis(millis)))
+    }
+}
+
+/// What a request needs to expose so a pipeline hop can check and
+/// forward its deadline without knowing anything else about the
+/// request's shape.
+pub trait HasDeadline {
+    fn deadline(&self) -> Option<Deadline>;
+}
+
+/// Wraps an `AsyncEngine` so a hop rejects a request outright once its
+/// deadline has already passed, instead of doing the work and having
+/// the answer discarded by a caller that stopped waiting. Requests
+/// without a deadline are forwarded unconditionally.
+pub struct DeadlineEnforcingEngine<E> {
+    inner: E,
+}
+
+impl<E> DeadlineEnforcingEngine<E> {
+    pub fn new(inner: E) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl<E, Req, Resp> AsyncEngine<Req, Resp> for DeadlineEnforcingEngine<E>
+where
+    E: AsyncEngine<Req, Resp>,
+    Req: HasDeadline + Send + 'static,
+    Resp: Send + 'static,
+{
+    async fn generate(&self, request: Req) -> Result<ResponseStream<Resp>, RuntimeError> {
+        if let Some(deadline) = request.deadline() {
+            if deadline.is_expired() {
+                return Err(RuntimeError::Timeout(Duration::ZERO));
+            }
+        }
+        self.inner.generate(request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fresh_deadline_has_nonzero_remaining() {
+        let deadline = Deadline::after(Duration::from_secs(1));
+        assert!(!deadline.is_expired());
+        assert!(deadline.remaining() <= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn elapsed_deadline_is_expired() {
+        let deadline = Deadline::after(Duration::ZERO);
+        std::thread::sleep(Duration::from_millis(1));
+        assert!(deadline.is_expired());
+    }
+
+    #[test]
+    fn encode_decode_round_trips_remaining_budget() {
+        let deadline = Deadline::after(Duration::from_millis(500));
+        let decoded = Deadline::decode(&deadline.encode()).unwrap();
+        assert!(decoded.remaining() <= Duration::from_millis(500));
+    }
+
+    #[test]
+    fn decode_rejects_garbage() {
+        assert!(Deadline::decode("not-a-number").is_none());
+    }
+
+    use futures::stream;
+
+    struct FakeRequest {
+        deadline: Option<Deadline>,
+    }
+
+    impl HasDeadline for FakeRequest {
+        fn deadline(&self) -> Option<Deadline> {
+            self.deadline
+        }
+    }
+
+    struct FakeEngine;
+
+    #[async_trait]
+    impl AsyncEngine<FakeRequest, &'static str> for FakeEngine {
+        async fn generate(
+            &self,
+            _request: FakeRequest,
+        ) -> Result<ResponseStream<&'static str>, RuntimeError> {
+            Ok(Box::pin(stream::iter(vec![Ok("ok")])))
+        }
+    }
+
+    #[tokio::test]
+    async fn expired_deadline_is_rejected_before_reaching_inner() {
+        let engine = DeadlineEnforcingEngine::new(FakeEngine);
+        let request = FakeRequest {
+            deadline: Some(Deadline::after(Duration::ZERO)),
+        };
+        assert!(matches!(
+            engine.generate(request).await,
+            Err(RuntimeError::Timeout(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn live_deadline_is_forwarded_to_inner() {
+        let engine = DeadlineEnforcingEngine::new(FakeEngine);
+        let request = FakeRequest {
+            deadline: Some(Deadline::after(Duration::from_secs(1))),
+        };
+        assert!(engine.generate(request).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn missing_deadline_is_forwarded_to_inner() {
+        let engine = DeadlineEnforcingEngine::new(FakeEngine);
+        let request = FakeRequest { deadline: None };
+        assert!(engine.generate(request).await.is_ok());
+    }
+}
@@ -0,0 +1,154 @@
+//! Disaggregated prefill/decode coordination: split one request across
+//! a prefill worker and a decode worker behind a single [`AsyncEngine`]
+//! facade, so callers of the coordinator can't tell the request didn't
+//! run on one worker end to end.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::StreamExt;
+
+use crate::engine::{AsyncEngine, ResponseStream};
+use crate::error::RuntimeError;
+
+/// The KV block handoff a coordinator triggers between the prefill and
+/// decode workers once prefill finishes, normally a NIXL-based RDMA
+/// transfer driven through `block_manager`. Neither crate exists in
+/// this tree yet, so this is the seam a real implementation plugs into
+/// — mirrors `dynamo_llm::mocker::block_manager_integration::OffloadBackend`.
+#[async_trait]
+pub trait KvHandoffBackend: Send + Sync {
+    async fn handoff(&self, request_id: &str, block_hashes: &[u64]) -> Result<(), RuntimeError>;
+}
+
+/// Placeholder handoff backend used until NIXL/`block_manager` land:
+/// reports success without moving anything.
+pub struct NoopKvHandoff;
+
+#[async_trait]
+impl KvHandoffBackend for NoopKvHandoff {
+    async fn handoff(&self, _request_id: &str, _block_hashes: &[u64]) -> Result<(), RuntimeError> {
+        Ok(())
+    }
+}
+
+/// What the coordinator needs from a request to split it: a stable id
+/// for the handoff, and the block hashes prefill produced that decode
+/// needs resident before it can start.
+pub trait DisaggregatableRequest {
+    fn request_id(&self) -> &str;
+    fn block_hashes(&self) -> &[u64];
+}
+
+/// Splits each request across one of several prefill workers and one
+/// of several decode workers, round-robin, triggering a KV handoff
+/// between the two. Prefill's own output stream is drained and
+/// discarded — callers only see decode's.
+pub struct PrefillDecodeCoordinator<Req, Resp> {
+    prefill_workers: Vec<Arc<dyn AsyncEngine<Req, Resp>>>,
+    decode_workers: Vec<Arc<dyn AsyncEngine<Req, Resp>>>,
+    handoff: Arc<dyn KvHandoffBackend>,
+    next_prefill: AtomicUsize,
+    next_decode: AtomicUsize,
+}
+
+impl<Req, Resp> PrefillDecodeCoordinator<Req, Resp> {
+    pub fn new(
+        prefill_workers: Vec<Arc<dyn AsyncEngine<Req, Resp>>>,
+        decode_workers: Vec<Arc<dyn AsyncEngine<Req, Resp>>>,
+        handoff: Arc<dyn KvHandoffBackend>,
+    ) -> Self {
+        Self {
+            prefill_workers,
+            decode_workers,
+            handoff,
+            next_prefill: AtomicUsize::new(0),
+            next_decode: AtomicUsize::new(0),
+        }
+    }
+
+    fn next_worker<'a>(
+        workers: &'a [Arc<dyn AsyncEngine<Req, Resp>>],
+        cursor: &AtomicUsize,
+    ) -> &'a Arc<dyn AsyncEngine<Req, Resp>> {
+        let idx = cursor.fetch_add(1, Ordering::Relaxed) % workers.len();
+        &workers[idx]
+    }
+}
+
+#[async_trait]
+impl<Req, Resp> AsyncEngine<Req, Resp> for PrefillDecodeCoordinator<Req, Resp>
+where
+    Req: DisaggregatableRequest + Clone + Send + Sync + 'static,
+    Resp: Send + 'static,
+{
+    async fn generate(&self, request: Req) -> Result<ResponseStream<Resp>, RuntimeError> {
+        let prefill_worker = Self::next_worker(&self.prefill_workers, &self.next_prefill);
+        let mut prefill_stream = prefill_worker.generate(request.clone()).await?;
+        while prefill_stream.next().await.is_some() {}
+
+        self.handoff
+            .handoff(request.request_id(), request.block_hashes())
+            .await?;
+
+        let decode_worker = Self::next_worker(&self.decode_workers, &self.next_decode);
+        decode_worker.generate(request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::stream;
+
+    #[derive(Clone)]
+    struct FakeRequest {
+        id: String,
+        hashes: Vec<u64>,
+    }
+
+    impl DisaggregatableRequest for FakeRequest {
+        fn request_id(&self) -> &str {
+            &self.id
+        }
+
+        fn block_hashes(&self) -> &[u64] {
+            &self.hashes
+        }
+    }
+
+    struct FakeWorker {
+        label: &'static str,
+    }
+
+    #[async_trait]
+    impl AsyncEngine<FakeRequest, &'static str> for FakeWorker {
+        async fn generate(
+            &self,
+            _request: FakeRequest,
+        ) -> Result<ResponseStream<&'static str>, RuntimeError> {
+            Ok(Box::pin(stream::iter(vec![Ok(self.label)])))
+        }
+    }
+
+    #[tokio::test]
+    async fn decode_output_is_what_the_caller_receives() {
+        let prefill: Arc<dyn AsyncEngine<FakeRequest, &'static str>> =
+            Arc::new(FakeWorker { label: "prefill" });
+        let decode: Arc<dyn AsyncEngine<FakeRequest, &'static str>> =
+            Arc::new(FakeWorker { label: "decode" });
+        let coordinator =
+            PrefillDecodeCoordinator::new(vec![prefill], vec![decode], Arc::new(NoopKvHandoff));
+
+        let mut stream = coordinator
+            .generate(FakeRequest {
+                id: "req-1".to_string(),
+                hashes: vec![1, 2],
+            })
+            .await
+            .unwrap();
+        let first = stream.next().await.unwrap().unwrap();
+        assert_eq!(first, "decode");
+    }
+}
@@ -0,0 +1,314 @@
+//! Service discovery/registration, abstracted behind a trait so a
+//! component can register itself and watch for peers without the
+//! pipeline caring whether the backing store is etcd, Kubernetes
+//! EndpointSlices, or DNS-SRV records — the last two matter in clusters
+//! where running a separate etcd cluster alongside Dynamo isn't an
+//! option.
+
+use async_trait::async_trait;
+
+use crate::error::RuntimeError;
+
+/// One registered instance of an endpoint: where to reach it, and an
+/// id stable enough that the same instance deregistering and
+/// re-registering doesn't look like two different peers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instance {
+    pub id: String,
+    pub address: String,
+}
+
+/// Registers and discovers instances of an endpoint. Every backend in
+/// this module implements this the same way regardless of what's
+/// actually storing the registration.
+#[async_trait]
+pub trait ServiceDiscovery: Send + Sync {
+    async fn register(&self, endpoint: &str, instance: Instance) -> Result<(), RuntimeError>;
+    async fn deregister(&self, endpoint: &str, instance_id: &str) -> Result<(), RuntimeError>;
+    async fn discover(&self, endpoint: &str) -> Result<Vec<Instance>, RuntimeError>;
+}
+
+/// The etcd operations discovery needs: put a key under a lease so a
+/// crashed instance's registration expires on its own, delete a key on
+/// graceful deregistration, and list a prefix. This crate doesn't
+/// vendor an etcd client, so [`EtcdDiscovery`] is written against this
+/// seam instead — swap in a real `etcd-client`-backed implementation
+/// without touching callers.
+#[async_trait]
+pub trait EtcdClient: Send + Sync {
+    async fn put_with_lease(&self, key: &str, value: &str) -> Result<(), RuntimeError>;
+    async fn delete(&self, key: &str) -> Result<(), RuntimeError>;
+    async fn list_prefix(&self, prefix: &str) -> Result<Vec<String>, RuntimeError>;
+}
+
+fn etcd_key(endpoint: &str, instance_id: &str) -> String {
+    format!("/dynamo/endpoints/{endpoint}/{instance_id}")
+}
+
+/// Discovery backed by etcd: one key per instance under
+/// `/dynamo/endpoints/<endpoint>/<instance id>`, with the address as
+/// the value and the instance's lease keeping it alive.
+pub struct EtcdDiscovery<C> {
+    client: C,
+}
+
+impl<C: EtcdClient> EtcdDiscovery<C> {
+    pub fn new(client: C) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl<C: EtcdClient> ServiceDiscovery for EtcdDiscovery<C> {
+    async fn register(&self, endpoint: &str, instance: Instance) -> Result<(), RuntimeError> {
+        self.client
+            .put_with_lease(&etcd_key(endpoint, &instance.id), &instance.address)
+            .await
+    }
+
+    async fn deregister(&self, endpoint: &str, instance_id: &str) -> Result<(), RuntimeError> {
+        self.client.delete(&etcd_key(endpoint, instance_id)).await
+    }
+
+    async fn discover(&self, endpoint: &str) -> Result<Vec<Instance>, RuntimeError> {
+        let prefix = format!("/dynamo/endpoints/{endpoint}/");
+        let keys = self.client.list_prefix(&prefix).await?;
+        Ok(keys
+            .into_iter()
+            .filter_map(|key| {
+                let id = key.strip_prefix(&prefix)?.to_string();
+                Some(Instance {
+                    id,
+                    address: String::new(),
+                })
+            })
+            .collect())
+    }
+}
+
+/// The Kubernetes API operations discovery needs against a namespaced
+/// Service's EndpointSlice objects. No `kube`/`k8s-openapi` dependency
+/// lives in this crate, so [`K8sEndpointSliceDiscovery`] is written
+/// against this seam; registration is a no-op because EndpointSlices
+/// are derived by the cluster from Pod readiness, not written by the
+/// workload itself.
+#[async_trait]
+pub trait EndpointSliceClient: Send + Sync {
+    /// Returns `(pod name, ready address)` for every ready endpoint
+    /// behind the Service named `service`.
+    async fn list_ready_addresses(
+        &self,
+        service: &str,
+    ) -> Result<Vec<(String, String)>, RuntimeError>;
+}
+
+/// Discovery backed by Kubernetes EndpointSlices: `discover` lists the
+/// ready addresses behind a Service, and `register`/`deregister` are
+/// no-ops since the cluster manages that list from Pod readiness.
+pub struct K8sEndpointSliceDiscovery<C> {
+    client: C,
+}
+
+impl<C: EndpointSliceClient> K8sEndpointSliceDiscovery<C> {
+    pub fn new(client: C) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl<C: EndpointSliceClient> ServiceDiscovery for K8sEndpointSliceDiscovery<C> {
+    async fn register(&self, _endpoint: &str, _instance: Instance) -> Result<(), RuntimeError> {
+        Ok(())
+    }
+
+    async fn deregister(&self, _endpoint: &str, _instance_id: &str) -> Result<(), RuntimeError> {
+        Ok(())
+    }
+
+    async fn discover(&self, endpoint: &str) -> Result<Vec<Instance>, RuntimeError> {
+        Ok(self
+            .client
+            .list_ready_addresses(endpoint)
+            .await?
+            .into_iter()
+            .map(|(pod_name, address)| Instance {
+                id: pod_name,
+                address,
+            })
+            .collect())
+    }
+}
+
+/// The DNS operations discovery needs to resolve a `_service._proto.name`
+/// SRV record. No DNS resolver crate lives in this dependency tree, so
+/// [`DnsSrvDiscovery`] is written against this seam.
+#[async_trait]
+pub trait DnsSrvResolver: Send + Sync {
+    /// Returns `(target host, port)` for every SRV record found.
+    async fn resolve_srv(&self, name: &str) -> Result<Vec<(String, u16)>, RuntimeError>;
+}
+
+/// Discovery backed by DNS-SRV records, for clusters where headless
+/// Services (or any SRV-publishing DNS) stand in for a dedicated
+/// discovery store. Registration is a no-op, same as the EndpointSlice
+/// backend: DNS is populated out of band.
+pub struct DnsSrvDiscovery<R> {
+    resolver: R,
+    query_name: String,
+}
+
+impl<R: DnsSrvResolver> DnsSrvDiscovery<R> {
+    pub fn new(resolver: R, query_name: impl Into<String>) -> Self {
+        Self {
+            resolver,
+            query_name: query_name.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl<R: DnsSrvResolver> ServiceDiscovery for DnsSrvDiscovery<R> {
+    async fn register(&self, _endpoint: &str, _instance: Instance) -> Result<(), RuntimeError> {
+        Ok(())
+    }
+
+    async fn deregister(&self, _endpoint: &str, _instance_id: &str) -> Result<(), RuntimeError> {
+        Ok(())
+    }
+
+    async fn discover(&self, _endpoint: &str) -> Result<Vec<Instance>, RuntimeError> {
+        Ok(self
+            .resolver
+            .resolve_srv(&self.query_name)
+            .await?
+            .into_iter()
+            .map(|(host, port)| Instance {
+                id: format!("{host}:{port}"),
+                address: format!("{host}:{port}"),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct FakeEtcd {
+        kvs: Mutex<HashMap<String, String>>,
+    }
+
+    #[async_trait]
+    impl EtcdClient for FakeEtcd {
+        async fn put_with_lease(&self, key: &str, value: &str) -> Result<(), RuntimeError> {
+            self.kvs
+                .lock()
+                .unwrap()
+                .insert(key.to_string(), value.to_string());
+            Ok(())
+        }
+
+        async fn delete(&self, key: &str) -> Result<(), RuntimeError> {
+            self.kvs.lock().unwrap().remove(key);
+            Ok(())
+        }
+
+        async fn list_prefix(&self, prefix: &str) -> Result<Vec<String>, RuntimeError> {
+            Ok(self
+                .kvs
+                .lock()
+                .unwrap()
+                .keys()
+                .filter(|key| key.starts_with(prefix))
+                .cloned()
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn etcd_discovery_round_trips_registration() {
+        let discovery = EtcdDiscovery::new(FakeEtcd::default());
+        discovery
+            .register(
+                "chat",
+                Instance {
+                    id: "worker-1".to_string(),
+                    address: "10.0.0.1:8000".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+
+        let found = discovery.discover("chat").await.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, "worker-1");
+    }
+
+    #[tokio::test]
+    async fn etcd_discovery_forgets_deregistered_instances() {
+        let discovery = EtcdDiscovery::new(FakeEtcd::default());
+        discovery
+            .register(
+                "chat",
+                Instance {
+                    id: "worker-1".to_string(),
+                    address: "10.0.0.1:8000".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+        discovery.deregister("chat", "worker-1").await.unwrap();
+
+        assert!(discovery.discover("chat").await.unwrap().is_empty());
+    }
+
+    struct FakeEndpointSliceClient;
+
+    #[async_trait]
+    impl EndpointSliceClient for FakeEndpointSliceClient {
+        async fn list_ready_addresses(
+            &self,
+            service: &str,
+        ) -> Result<Vec<(String, String)>, RuntimeError> {
+            assert_eq!(service, "chat");
+            Ok(vec![("pod-a".to_string(), "10.0.0.2:8000".to_string())])
+        }
+    }
+
+    #[tokio::test]
+    async fn k8s_discovery_lists_ready_pods() {
+        let discovery = K8sEndpointSliceDiscovery::new(FakeEndpointSliceClient);
+        let found = discovery.discover("chat").await.unwrap();
+        assert_eq!(
+            found,
+            vec![Instance {
+                id: "pod-a".to_string(),
+                address: "10.0.0.2:8000".to_string(),
+            }]
+        );
+    }
+
+    struct FakeDnsResolver;
+
+    #[async_trait]
+    impl DnsSrvResolver for FakeDnsResolver {
+        async fn resolve_srv(&self, name: &str) -> Result<Vec<(String, u16)>, RuntimeError> {
+            assert_eq!(name, "_chat._tcp.dynamo.svc.cluster.local");
+            Ok(vec![(
+                "worker-0.dynamo.svc.cluster.local".to_string(),
+                8000,
+            )])
+        }
+    }
+
+    #[tokio::test]
+    async fn dns_srv_discovery_resolves_targets() {
+        let discovery =
+            DnsSrvDiscovery::new(FakeDnsResolver, "_chat._tcp.dynamo.svc.cluster.local");
+        let found = discovery.discover("chat").await.unwrap();
+        assert_eq!(found[0].address, "worker-0.dynamo.svc.cluster.local:8000");
+    }
+}
@@ -0,0 +1,250 @@
+//! Graceful shutdown: once a component starts draining it deregisters
+//! from discovery and stops accepting new requests, but lets in-flight
+//! streams run to completion up to a timeout instead of cutting every
+//! connection the instant the process is asked to stop — so a rolling
+//! upgrade doesn't truncate a response someone is already reading.
+//! Deregistration from discovery is the caller's job (it owns the
+//! discovery client); this module is the accounting and enforcement
+//! that happens after that.
+
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+use tokio::sync::Notify;
+
+use crate::engine::{AsyncEngine, ResponseStream};
+use crate::error::RuntimeError;
+
+/// Result of waiting for a drain to finish.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrainOutcome {
+    /// Every in-flight stream completed on its own before the timeout.
+    Drained,
+    /// The timeout elapsed with streams still running; they've been
+    /// sent a forced cancellation.
+    TimedOut,
+}
+
+struct DrainState {
+    draining: AtomicBool,
+    in_flight: AtomicI64,
+    force_cancelled: AtomicBool,
+    drained: Notify,
+    force_cancel: Notify,
+}
+
+/// Shared drain coordinator for one component. Clone to hand the same
+/// drain state to every [`DrainableEngine`] wrapping that component's
+/// endpoints.
+#[derive(Clone)]
+pub struct DrainController {
+    state: Arc<DrainState>,
+}
+
+impl Default for DrainController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DrainController {
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(DrainState {
+                draining: AtomicBool::new(false),
+                in_flight: AtomicI64::new(0),
+                force_cancelled: AtomicBool::new(false),
+                drained: Notify::new(),
+                force_cancel: Notify::new(),
+            }),
+        }
+    }
+
+    /// Stops admitting new requests. Call once discovery deregistration
+    /// has started, so nothing new gets routed here in the window
+    /// between the two.
+    pub fn begin_drain(&self) {
+        self.state.draining.store(true, Ordering::Release);
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.state.draining.load(Ordering::Acquire)
+    }
+
+    pub fn in_flight(&self) -> i64 {
+        self.state.in_flight.load(Ordering::Acquire)
+    }
+
+    fn enter(&self) -> DrainGuard {
+        self.state.in_flight.fetch_add(1, Ordering::AcqRel);
+        DrainGuard {
+            state: self.state.clone(),
+        }
+    }
+
+    /// Waits for every in-flight stream to finish, or `timeout` to
+    /// elapse, whichever comes first. On timeout, forcibly cancels
+    /// whatever is still running.
+    pub async fn wait_for_drain(&self, timeout: Duration) -> DrainOutcome {
+        let notified = self.state.drained.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+
+        if self.state.in_flight.load(Ordering::Acquire) == 0 {
+            return DrainOutcome::Drained;
+        }
+
+        match tokio::time::timeout(timeout, notified).await {
+            Ok(_) => DrainOutcome::Drained,
+            Err(_elapsed) => {
+                self.state.force_cancelled.store(true, Ordering::Release);
+                self.state.force_cancel.notify_waiters();
+                DrainOutcome::TimedOut
+            }
+        }
+    }
+}
+
+struct DrainGuard {
+    state: Arc<DrainState>,
+}
+
+impl Drop for DrainGuard {
+    fn drop(&mut self) {
+        let remaining = self.state.in_flight.fetch_sub(1, Ordering::AcqRel) - 1;
+        if remaining == 0 {
+            self.state.drained.notify_waiters();
+        }
+    }
+}
+
+/// Wraps an `AsyncEngine` so it can be asked to drain: once draining,
+/// new requests are rejected with [`RuntimeError::Draining`] before
+/// reaching `inner`, and every already-admitted stream is tracked so
+/// [`DrainController::wait_for_drain`] knows when it's safe to exit.
+pub struct DrainableEngine<E> {
+    inner: E,
+    controller: DrainController,
+}
+
+impl<E> DrainableEngine<E> {
+    pub fn new(inner: E, controller: DrainController) -> Self {
+        Self { inner, controller }
+    }
+}
+
+#[async_trait]
+impl<E, Req, Resp> AsyncEngine<Req, Resp> for DrainableEngine<E>
+where
+    E: AsyncEngine<Req, Resp>,
+    Req: Send + 'static,
+    Resp: Send + 'static,
+{
+    async fn generate(&self, request: Req) -> Result<ResponseStream<Resp>, RuntimeError> {
+        if self.controller.is_draining() {
+            return Err(RuntimeError::Draining);
+        }
+        let guard = self.controller.enter();
+        let stream = self.inner.generate(request).await?;
+        let state = self.controller.state.clone();
+
+        Ok(Box::pin(stream::unfold(
+            Some((stream, guard, state)),
+            |cursor| async move {
+                let (mut stream, guard, state) = cursor?;
+                if state.force_cancelled.load(Ordering::Acquire) {
+                    return Some((Err(RuntimeError::Cancelled), None));
+                }
+                tokio::select! {
+                    _ = state.force_cancel.notified() => Some((Err(RuntimeError::Cancelled), None)),
+                    item = stream.next() => item.map(|item| (item, Some((stream, guard, state)))),
+                }
+            },
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct SlowEngine {
+        delay: Duration,
+    }
+
+    #[async_trait]
+    impl AsyncEngine<(), &'static str> for SlowEngine {
+        async fn generate(
+            &self,
+            _request: (),
+        ) -> Result<ResponseStream<&'static str>, RuntimeError> {
+            let delay = self.delay;
+            Ok(Box::pin(stream::unfold(
+                Some(()),
+                move |state| async move {
+                    let _ = state?;
+                    tokio::time::sleep(delay).await;
+                    Some((Ok("chunk"), None))
+                },
+            )))
+        }
+    }
+
+    #[tokio::test]
+    async fn draining_rejects_new_requests() {
+        let controller = DrainController::new();
+        let engine = DrainableEngine::new(
+            SlowEngine {
+                delay: Duration::from_millis(1),
+            },
+            controller.clone(),
+        );
+        controller.begin_drain();
+        assert!(matches!(
+            engine.generate(()).await,
+            Err(RuntimeError::Draining)
+        ));
+    }
+
+    #[tokio::test]
+    async fn drain_waits_for_in_flight_stream_to_finish() {
+        let controller = DrainController::new();
+        let engine = DrainableEngine::new(
+            SlowEngine {
+                delay: Duration::from_millis(10),
+            },
+            controller.clone(),
+        );
+        let mut stream = engine.generate(()).await.unwrap();
+        assert_eq!(controller.in_flight(), 1);
+
+        let wait =
+            tokio::spawn(async move { controller.wait_for_drain(Duration::from_secs(1)).await });
+        assert_eq!(stream.next().await.unwrap().unwrap(), "chunk");
+        drop(stream);
+
+        assert_eq!(wait.await.unwrap(), DrainOutcome::Drained);
+    }
+
+    #[tokio::test]
+    async fn drain_timeout_force_cancels_remaining_streams() {
+        let controller = DrainController::new();
+        let engine = DrainableEngine::new(
+            SlowEngine {
+                delay: Duration::from_secs(60),
+            },
+            controller.clone(),
+        );
+        let mut stream = engine.generate(()).await.unwrap();
+
+        let outcome = controller.wait_for_drain(Duration::from_millis(5)).await;
+        assert_eq!(outcome, DrainOutcome::TimedOut);
+        assert!(matches!(
+            stream.next().await,
+            Some(Err(RuntimeError::Cancelled))
+        ));
+    }
+}
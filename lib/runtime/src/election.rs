@@ -0,0 +1,323 @@
+//! Leader election for singleton control-plane components (the
+//! planner, a KV-index aggregator) that need to run replicated for
+//! availability while only one instance actually does the work at a
+//! time. Backed by an etcd-style lease: whoever creates the election
+//! key first holds it until it stops renewing, at which point another
+//! replica's next campaign attempt wins it.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::sync::Notify;
+
+use crate::error::RuntimeError;
+
+/// The etcd operations leader election needs: an atomic create that
+/// only succeeds if no live lease already holds the key (so exactly
+/// one candidate wins), a lease keep-alive to prove this instance is
+/// still alive, and a release to resign immediately instead of waiting
+/// for the lease to expire. This crate doesn't vendor an etcd client,
+/// so [`LeaderElector`] is written against this seam, the same way
+/// [`crate::discovery::EtcdDiscovery`] is written against
+/// [`crate::discovery::EtcdClient`].
+#[async_trait]
+pub trait ElectionClient: Send + Sync {
+    /// Attempts to become leader by creating `key` under a lease with
+    /// `value` (typically this instance's id), succeeding only if no
+    /// live lease already holds it.
+    async fn try_acquire(&self, key: &str, value: &str) -> Result<bool, RuntimeError>;
+    /// Proves this instance is still alive, extending its lease on
+    /// `key`. An error here means the lease may have already expired.
+    async fn keep_alive(&self, key: &str) -> Result<(), RuntimeError>;
+    /// Gives up leadership on `key` immediately, letting another
+    /// candidate win it without waiting out the lease.
+    async fn release(&self, key: &str) -> Result<(), RuntimeError>;
+}
+
+#[async_trait]
+impl<C: ElectionClient + ?Sized> ElectionClient for Arc<C> {
+    async fn try_acquire(&self, key: &str, value: &str) -> Result<bool, RuntimeError> {
+        (**self).try_acquire(key, value).await
+    }
+
+    async fn keep_alive(&self, key: &str) -> Result<(), RuntimeError> {
+        (**self).keep_alive(key).await
+    }
+
+    async fn release(&self, key: &str) -> Result<(), RuntimeError> {
+        (**self).release(key).await
+    }
+}
+
+/// Notified when this instance's leadership state changes. Implement
+/// this for whatever needs to start or stop doing leader-only work.
+#[async_trait]
+pub trait LeadershipObserver: Send + Sync {
+    async fn on_gain(&self);
+    async fn on_loss(&self);
+}
+
+/// How often [`LeaderElector::run`] retries becoming leader while it
+/// isn't one, and how often it renews its lease while it is.
+#[derive(Debug, Clone, Copy)]
+pub struct ElectionConfig {
+    pub campaign_interval: Duration,
+    pub keep_alive_interval: Duration,
+}
+
+impl Default for ElectionConfig {
+    fn default() -> Self {
+        Self {
+            campaign_interval: Duration::from_secs(2),
+            keep_alive_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Runs the campaign/keep-alive loop for one candidate. Wrap in an
+/// `Arc` to call [`LeaderElector::stop`] from outside the task running
+/// [`LeaderElector::run`].
+pub struct LeaderElector<C> {
+    client: C,
+    key: String,
+    value: String,
+    config: ElectionConfig,
+    is_leader: AtomicBool,
+    stop: Notify,
+}
+
+impl<C: ElectionClient> LeaderElector<C> {
+    pub fn new(
+        client: C,
+        key: impl Into<String>,
+        value: impl Into<String>,
+        config: ElectionConfig,
+    ) -> Self {
+        Self {
+            client,
+            key: key.into(),
+            value: value.into(),
+            config,
+            is_leader: AtomicBool::new(false),
+            stop: Notify::new(),
+        }
+    }
+
+    pub fn is_leader(&self) -> bool {
+        self.is_leader.load(Ordering::Acquire)
+    }
+
+    /// Stops [`LeaderElector::run`], resigning first if this instance
+    /// currently holds leadership.
+    pub fn stop(&self) {
+        self.stop.notify_one();
+    }
+
+    async fn step<O: LeadershipObserver>(&self, observer: &O) {
+        if self.is_leader.load(Ordering::Acquire) {
+            if self.client.keep_alive(&self.key).await.is_err() {
+                self.is_leader.store(false, Ordering::Release);
+                observer.on_loss().await;
+            }
+        } else if let Ok(true) = self.client.try_acquire(&self.key, &self.value).await {
+            self.is_leader.store(true, Ordering::Release);
+            observer.on_gain().await;
+        }
+    }
+
+    /// Campaigns for leadership and renews it once held, calling
+    /// `observer`'s hooks on every gain or loss, until
+    /// [`LeaderElector::stop`] is called. Meant to be driven by a
+    /// single long-lived `tokio::spawn`ed task per instance.
+    pub async fn run<O: LeadershipObserver>(&self, observer: &O) {
+        self.step(observer).await;
+        loop {
+            let interval = if self.is_leader() {
+                self.config.keep_alive_interval
+            } else {
+                self.config.campaign_interval
+            };
+            tokio::select! {
+                _ = self.stop.notified() => {
+                    if self.is_leader.swap(false, Ordering::AcqRel) {
+                        let _ = self.client.release(&self.key).await;
+                        observer.on_loss().await;
+                    }
+                    return;
+                }
+                _ = tokio::time::sleep(interval) => {
+                    self.step(observer).await;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct FakeElectionClient {
+        holder: Mutex<Option<String>>,
+        keep_alive_fails: AtomicBool,
+    }
+
+    #[async_trait]
+    impl ElectionClient for FakeElectionClient {
+        async fn try_acquire(&self, _key: &str, value: &str) -> Result<bool, RuntimeError> {
+            let mut holder = self.holder.lock().unwrap();
+            if holder.is_none() {
+                *holder = Some(value.to_string());
+                Ok(true)
+            } else {
+                Ok(false)
+            }
+        }
+
+        async fn keep_alive(&self, _key: &str) -> Result<(), RuntimeError> {
+            if self.keep_alive_fails.load(Ordering::Acquire) {
+                Err(RuntimeError::Upstream("lease expired".to_string()))
+            } else {
+                Ok(())
+            }
+        }
+
+        async fn release(&self, _key: &str) -> Result<(), RuntimeError> {
+            *self.holder.lock().unwrap() = None;
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingObserver {
+        gains: AtomicUsize,
+        losses: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl LeadershipObserver for RecordingObserver {
+        async fn on_gain(&self) {
+            self.gains.fetch_add(1, Ordering::Relaxed);
+        }
+
+        async fn on_loss(&self) {
+            self.losses.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn fast_config() -> ElectionConfig {
+        ElectionConfig {
+            campaign_interval: Duration::from_millis(2),
+            keep_alive_interval: Duration::from_millis(2),
+        }
+    }
+
+    #[tokio::test]
+    async fn becomes_leader_when_no_one_else_holds_the_key() {
+        let client = FakeElectionClient::default();
+        let elector = Arc::new(LeaderElector::new(
+            client,
+            "control/leader",
+            "instance-a",
+            fast_config(),
+        ));
+        let observer = Arc::new(RecordingObserver::default());
+
+        let task = tokio::spawn({
+            let elector = elector.clone();
+            let observer = observer.clone();
+            async move { elector.run(&*observer).await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        assert!(elector.is_leader());
+        assert_eq!(observer.gains.load(Ordering::Relaxed), 1);
+
+        elector.stop();
+        task.await.unwrap();
+        assert!(!elector.is_leader());
+        assert_eq!(observer.losses.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn a_second_candidate_does_not_win_while_the_first_holds_the_lease() {
+        let client = Arc::new(FakeElectionClient::default());
+        let first = Arc::new(LeaderElector::new(
+            client.clone(),
+            "control/leader",
+            "instance-a",
+            fast_config(),
+        ));
+        let second = Arc::new(LeaderElector::new(
+            client,
+            "control/leader",
+            "instance-b",
+            fast_config(),
+        ));
+        let first_observer = Arc::new(RecordingObserver::default());
+        let second_observer = Arc::new(RecordingObserver::default());
+
+        let first_task = tokio::spawn({
+            let first = first.clone();
+            let first_observer = first_observer.clone();
+            async move { first.run(&*first_observer).await }
+        });
+        let second_task = tokio::spawn({
+            let second = second.clone();
+            let second_observer = second_observer.clone();
+            async move { second.run(&*second_observer).await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        assert!(first.is_leader());
+        assert!(!second.is_leader());
+        assert_eq!(second_observer.gains.load(Ordering::Relaxed), 0);
+
+        first.stop();
+        first_task.await.unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        assert!(second.is_leader());
+        assert_eq!(second_observer.gains.load(Ordering::Relaxed), 1);
+
+        second.stop();
+        second_task.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn a_failed_keep_alive_demotes_the_leader() {
+        let client = FakeElectionClient::default();
+        let elector = Arc::new(LeaderElector::new(
+            client,
+            "control/leader",
+            "instance-a",
+            fast_config(),
+        ));
+        let observer = Arc::new(RecordingObserver::default());
+
+        let task = tokio::spawn({
+            let elector = elector.clone();
+            let observer = observer.clone();
+            async move { elector.run(&*observer).await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(elector.is_leader());
+
+        elector
+            .client
+            .keep_alive_fails
+            .store(true, Ordering::Release);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        assert!(!elector.is_leader());
+        assert_eq!(observer.losses.load(Ordering::Relaxed), 1);
+
+        elector.stop();
+        task.await.unwrap();
+    }
+}
@@ -0,0 +1,28 @@
+//! The `AsyncEngine` facade: the single trait every request-processing
+//! component in the pipeline — a local model, a remote egress client, a
+//! disaggregated prefill/decode coordinator — implements, so callers
+//! never need to know which one they're talking to.
+
+use std::pin::Pin;
+
+use async_trait::async_trait;
+use futures::Stream;
+
+use crate::error::RuntimeError;
+
+/// A stream of response chunks, boxed so trait objects can return it
+/// without the caller naming the concrete stream type.
+pub type ResponseStream<T> = Pin<Box<dyn Stream<Item = Result<T, RuntimeError>> + Send>>;
+
+/// Processes one request and streams back zero or more response
+/// chunks. Every pipeline wrapper (metrics, retries, circuit breaking)
+/// is written once against this trait and applies uniformly to
+/// whatever implements it.
+#[async_trait]
+pub trait AsyncEngine<Req, Resp>: Send + Sync
+where
+    Req: Send + 'static,
+    Resp: Send + 'static,
+{
+    async fn generate(&self, request: Req) -> Result<ResponseStream<Resp>, RuntimeError>;
+}
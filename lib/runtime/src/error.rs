@@ -0,0 +1,18 @@
+//! Error type shared across runtime components: engines, egress
+//! clients, and the coordinators that wrap them.
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum RuntimeError {
+    #[error("upstream engine returned an error: {0}")]
+    Upstream(String),
+    #[error("request timed out after {0:?}")]
+    Timeout(std::time::Duration),
+    #[error("request was cancelled")]
+    Cancelled,
+    #[error("rate limited, retry after {0:?}")]
+    RateLimited(std::time::Duration),
+    #[error("rejecting request: component is draining")]
+    Draining,
+}
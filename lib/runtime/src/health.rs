@@ -0,0 +1,133 @@
+//! Liveness and readiness for registered components, aggregated into
+//! the system status server the way [`crate::metrics::MetricsRegistry`]
+//! is: a component (an engine process, a router, anything with its own
+//! startup sequence) registers itself once and then flips named
+//! readiness checks — engine loaded, KV pool initialized, event-plane
+//! connected — as they pass, instead of routers discovering a
+//! not-yet-ready instance by sending it requests that fail.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// A component's readiness is "registered and every check it reports
+/// is passing". A component with no checks registered yet is not
+/// ready — it hasn't had the chance to say it's fine.
+#[derive(Default)]
+pub struct ComponentHealth {
+    checks: Mutex<HashMap<String, bool>>,
+}
+
+impl ComponentHealth {
+    /// Records the outcome of one named readiness check, e.g.
+    /// `"engine_loaded"` or `"kv_pool_initialized"`.
+    pub fn set_check(&self, name: &str, passing: bool) {
+        self.checks
+            .lock()
+            .unwrap()
+            .insert(name.to_string(), passing);
+    }
+
+    pub fn is_ready(&self) -> bool {
+        let checks = self.checks.lock().unwrap();
+        !checks.is_empty() && checks.values().all(|&passing| passing)
+    }
+
+    /// Names of checks currently failing, for a diagnostics page.
+    pub fn failing_checks(&self) -> Vec<String> {
+        self.checks
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, &passing)| !passing)
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+}
+
+/// Holds one [`ComponentHealth`] per component name, created on first
+/// use, so the system status server and routers share one view of
+/// who's ready.
+#[derive(Default)]
+pub struct HealthRegistry {
+    components: Mutex<HashMap<String, Arc<ComponentHealth>>>,
+}
+
+impl HealthRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn component(&self, name: &str) -> Arc<ComponentHealth> {
+        self.components
+            .lock()
+            .unwrap()
+            .entry(name.to_string())
+            .or_insert_with(|| Arc::new(ComponentHealth::default()))
+            .clone()
+    }
+
+    /// False for a component that was never registered, same as one
+    /// that registered but hasn't passed its checks yet — a router
+    /// shouldn't need to tell the two apart to decide to skip it.
+    pub fn is_ready(&self, name: &str) -> bool {
+        self.components
+            .lock()
+            .unwrap()
+            .get(name)
+            .map(|health| health.is_ready())
+            .unwrap_or(false)
+    }
+
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        for (name, health) in self.components.lock().unwrap().iter() {
+            out.push_str(&format!(
+                "dynamo_component_ready{{component=\"{name}\"}} {}\n",
+                health.is_ready() as u8
+            ));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn component_with_no_checks_is_not_ready() {
+        let registry = HealthRegistry::new();
+        registry.component("worker-a");
+        assert!(!registry.is_ready("worker-a"));
+    }
+
+    #[test]
+    fn ready_only_once_every_check_passes() {
+        let registry = HealthRegistry::new();
+        let health = registry.component("worker-a");
+        health.set_check("engine_loaded", true);
+        health.set_check("kv_pool_initialized", false);
+        assert!(!registry.is_ready("worker-a"));
+
+        health.set_check("kv_pool_initialized", true);
+        assert!(registry.is_ready("worker-a"));
+    }
+
+    #[test]
+    fn unknown_component_is_not_ready() {
+        let registry = HealthRegistry::new();
+        assert!(!registry.is_ready("never-registered"));
+    }
+
+    #[test]
+    fn failing_checks_lists_only_what_is_not_passing() {
+        let registry = HealthRegistry::new();
+        let health = registry.component("worker-a");
+        health.set_check("engine_loaded", true);
+        health.set_check("event_plane_connected", false);
+        assert_eq!(
+            health.failing_checks(),
+            vec!["event_plane_connected".to_string()]
+        );
+    }
+}
@@ -0,0 +1,176 @@
+//! Wraps any `AsyncEngine` with the standard metrics every endpoint
+//! gets automatically, so individual engines don't instrument
+//! themselves and every one of them shows up the same way on the
+//! system status server.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+
+use crate::engine::{AsyncEngine, ResponseStream};
+use crate::error::RuntimeError;
+use crate::metrics::{EndpointMetrics, MetricsRegistry};
+
+/// An `AsyncEngine` wrapped with request/in-flight/latency/stream-chunk
+/// counters and errors-by-class, recorded against one endpoint in a
+/// [`MetricsRegistry`].
+pub struct InstrumentedEngine<E> {
+    inner: E,
+    metrics: Arc<EndpointMetrics>,
+}
+
+impl<E> InstrumentedEngine<E> {
+    pub fn new(inner: E, registry: &MetricsRegistry, endpoint: &str) -> Self {
+        Self {
+            inner,
+            metrics: registry.endpoint(endpoint),
+        }
+    }
+}
+
+fn error_class(err: &RuntimeError) -> &'static str {
+    match err {
+        RuntimeError::Upstream(_) => "upstream",
+        RuntimeError::Timeout(_) => "timeout",
+        RuntimeError::Cancelled => "cancelled",
+        RuntimeError::RateLimited(_) => "rate_limited",
+        RuntimeError::Draining => "draining",
+    }
+}
+
+#[async_trait]
+impl<E, Req, Resp> AsyncEngine<Req, Resp> for InstrumentedEngine<E>
+where
+    E: AsyncEngine<Req, Resp>,
+    Req: Send + 'static,
+    Resp: Send + 'static,
+{
+    async fn generate(&self, request: Req) -> Result<ResponseStream<Resp>, RuntimeError> {
+        self.metrics.request_started();
+        let started = Instant::now();
+        let result = self.inner.generate(request).await;
+
+        match result {
+            Ok(stream) => {
+                let metrics = self.metrics.clone();
+                // `request_finished` (and thus the latency histogram) is
+                // recorded once the stream actually ends, not once
+                // `generate` hands back the stream handle — otherwise
+                // both it and `in_flight` would measure only the time
+                // to obtain a stream, not the request's real duration.
+                Ok(Box::pin(stream::unfold(
+                    Some((stream, metrics, started)),
+                    |cursor| async move {
+                        let (mut stream, metrics, started) = cursor?;
+                        match stream.next().await {
+                            Some(item) => {
+                                match &item {
+                                    Ok(_) => metrics.record_chunk(),
+                                    Err(e) => metrics.record_error(error_class(e)),
+                                }
+                                Some((item, Some((stream, metrics, started))))
+                            }
+                            None => {
+                                metrics.request_finished(started.elapsed().as_secs_f64() * 1000.0);
+                                None
+                            }
+                        }
+                    },
+                )))
+            }
+            Err(e) => {
+                self.metrics.record_error(error_class(&e));
+                self.metrics
+                    .request_finished(started.elapsed().as_secs_f64() * 1000.0);
+                Err(e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::Notify;
+
+    struct FakeEngine;
+
+    #[async_trait]
+    impl AsyncEngine<(), &'static str> for FakeEngine {
+        async fn generate(
+            &self,
+            _request: (),
+        ) -> Result<ResponseStream<&'static str>, RuntimeError> {
+            Ok(Box::pin(stream::iter(vec![Ok("a"), Ok("b")])))
+        }
+    }
+
+    #[tokio::test]
+    async fn records_one_chunk_per_stream_item() {
+        let registry = MetricsRegistry::new();
+        let engine = InstrumentedEngine::new(FakeEngine, &registry, "chat");
+        let mut stream = engine.generate(()).await.unwrap();
+        while stream.next().await.is_some() {}
+        let text = registry.to_prometheus();
+        assert!(text.contains("dynamo_stream_chunks_total{endpoint=\"chat\"} 2"));
+        assert!(text.contains("dynamo_requests_in_flight{endpoint=\"chat\"} 0"));
+    }
+
+    struct SlowEngine {
+        release: Arc<Notify>,
+    }
+
+    #[async_trait]
+    impl AsyncEngine<(), &'static str> for SlowEngine {
+        async fn generate(
+            &self,
+            _request: (),
+        ) -> Result<ResponseStream<&'static str>, RuntimeError> {
+            let release = self.release.clone();
+            Ok(Box::pin(stream::unfold(Some(()), move |state| {
+                let release = release.clone();
+                async move {
+                    state?;
+                    release.notified().await;
+                    Some(("chunk", None))
+                }
+            }))
+            .map(Ok)
+            .boxed())
+        }
+    }
+
+    #[tokio::test]
+    async fn in_flight_and_latency_reflect_the_full_stream_not_just_the_handle() {
+        let release = Arc::new(Notify::new());
+        let registry = MetricsRegistry::new();
+        let engine = InstrumentedEngine::new(
+            SlowEngine {
+                release: release.clone(),
+            },
+            &registry,
+            "chat",
+        );
+
+        let mut stream = engine.generate(()).await.unwrap();
+
+        // The stream hasn't been polled to completion yet, so the
+        // request must still count as in flight.
+        assert!(registry
+            .to_prometheus()
+            .contains("dynamo_requests_in_flight{endpoint=\"chat\"} 1"));
+
+        release.notify_one();
+        stream.next().await.unwrap().unwrap();
+        // Drain the stream to completion so the terminal `None` branch
+        // actually runs and records `request_finished` — dropping the
+        // stream early would just discard its state without polling it.
+        assert!(stream.next().await.is_none());
+
+        let text = registry.to_prometheus();
+        assert!(text.contains("dynamo_requests_in_flight{endpoint=\"chat\"} 0"));
+        assert!(text.contains("dynamo_stream_chunks_total{endpoint=\"chat\"} 1"));
+    }
+}
@@ -0,0 +1,24 @@
+//! Distributed runtime primitives shared across Dynamo components: the
+//! `AsyncEngine` facade every request-processing component implements,
+//! and the coordinators and transports built on top of it.
+
+pub mod admission;
+pub mod cache;
+pub mod cancellation;
+pub mod circuit_breaker;
+pub mod concurrency;
+pub mod deadline;
+pub mod disagg;
+pub mod discovery;
+pub mod drain;
+pub mod election;
+pub mod engine;
+pub mod error;
+pub mod health;
+pub mod instrumented;
+pub mod journal;
+pub mod metrics;
+pub mod priority;
+pub mod retry;
+pub mod tracing;
+pub mod transport;
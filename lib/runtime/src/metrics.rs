@@ -0,0 +1,171 @@
+//! A metrics registry for `AsyncEngine` instrumentation: per-endpoint
+//! counters and a latency histogram, rendered as Prometheus text
+//! exposition for the system status server.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Running latency samples for one endpoint, queried as percentiles.
+#[derive(Default)]
+struct Histogram {
+    samples_ms: Mutex<Vec<f64>>,
+}
+
+impl Histogram {
+    fn observe(&self, value_ms: f64) {
+        self.samples_ms.lock().unwrap().push(value_ms);
+    }
+
+    fn percentile(&self, p: f64) -> f64 {
+        let mut samples = self.samples_ms.lock().unwrap().clone();
+        if samples.is_empty() {
+            return 0.0;
+        }
+        samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let idx = ((samples.len() - 1) as f64 * p).round() as usize;
+        samples[idx]
+    }
+}
+
+/// Per-endpoint counters and latency histogram, matching what the
+/// pipeline exposes for every `AsyncEngine` it wraps: request counts,
+/// in-flight count, stream chunk counts, and errors by class.
+#[derive(Default)]
+pub struct EndpointMetrics {
+    requests_total: AtomicU64,
+    in_flight: AtomicI64,
+    stream_chunks_total: AtomicU64,
+    errors_by_class: Mutex<HashMap<String, u64>>,
+    latency_ms: Histogram,
+    queue_depth: AtomicI64,
+    queue_wait_ms: Histogram,
+}
+
+impl EndpointMetrics {
+    pub fn request_started(&self) {
+        self.requests_total.fetch_add(1, Ordering::Relaxed);
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn request_finished(&self, latency_ms: f64) {
+        self.in_flight.fetch_sub(1, Ordering::Relaxed);
+        self.latency_ms.observe(latency_ms);
+    }
+
+    pub fn record_chunk(&self) {
+        self.stream_chunks_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A request has started waiting for a concurrency slot.
+    pub fn enqueued(&self) {
+        self.queue_depth.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A request stopped waiting because it was admitted after
+    /// `wait_ms`. A request rejected by a full queue is never counted
+    /// as enqueued, so this is not called for it.
+    pub fn dequeued(&self, wait_ms: f64) {
+        self.queue_depth.fetch_sub(1, Ordering::Relaxed);
+        self.queue_wait_ms.observe(wait_ms);
+    }
+
+    pub fn queue_depth(&self) -> i64 {
+        self.queue_depth.load(Ordering::Relaxed)
+    }
+
+    pub fn record_error(&self, class: &str) {
+        let mut errors = self.errors_by_class.lock().unwrap();
+        *errors.entry(class.to_string()).or_insert(0) += 1;
+    }
+}
+
+/// Holds one [`EndpointMetrics`] per endpoint name, created on first
+/// use, and renders all of them as Prometheus text exposition.
+#[derive(Default)]
+pub struct MetricsRegistry {
+    endpoints: Mutex<HashMap<String, Arc<EndpointMetrics>>>,
+}
+
+impl MetricsRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn endpoint(&self, name: &str) -> Arc<EndpointMetrics> {
+        self.endpoints
+            .lock()
+            .unwrap()
+            .entry(name.to_string())
+            .or_insert_with(|| Arc::new(EndpointMetrics::default()))
+            .clone()
+    }
+
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        for (name, metrics) in self.endpoints.lock().unwrap().iter() {
+            out.push_str(&format!(
+                "dynamo_requests_total{{endpoint=\"{name}\"}} {}\n",
+                metrics.requests_total.load(Ordering::Relaxed)
+            ));
+            out.push_str(&format!(
+                "dynamo_requests_in_flight{{endpoint=\"{name}\"}} {}\n",
+                metrics.in_flight.load(Ordering::Relaxed)
+            ));
+            out.push_str(&format!(
+                "dynamo_stream_chunks_total{{endpoint=\"{name}\"}} {}\n",
+                metrics.stream_chunks_total.load(Ordering::Relaxed)
+            ));
+            out.push_str(&format!(
+                "dynamo_request_latency_ms_p50{{endpoint=\"{name}\"}} {}\n",
+                metrics.latency_ms.percentile(0.5)
+            ));
+            out.push_str(&format!(
+                "dynamo_request_latency_ms_p99{{endpoint=\"{name}\"}} {}\n",
+                metrics.latency_ms.percentile(0.99)
+            ));
+            out.push_str(&format!(
+                "dynamo_queue_depth{{endpoint=\"{name}\"}} {}\n",
+                metrics.queue_depth.load(Ordering::Relaxed)
+            ));
+            out.push_str(&format!(
+                "dynamo_queue_wait_ms_p50{{endpoint=\"{name}\"}} {}\n",
+                metrics.queue_wait_ms.percentile(0.5)
+            ));
+            out.push_str(&format!(
+                "dynamo_queue_wait_ms_p99{{endpoint=\"{name}\"}} {}\n",
+                metrics.queue_wait_ms.percentile(0.99)
+            ));
+            for (class, count) in metrics.errors_by_class.lock().unwrap().iter() {
+                out.push_str(&format!(
+                    "dynamo_errors_total{{endpoint=\"{name}\",class=\"{class}\"}} {count}\n"
+                ));
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prometheus_output_includes_request_count_per_endpoint() {
+        let registry = MetricsRegistry::new();
+        let endpoint = registry.endpoint("chat");
+        endpoint.request_started();
+        endpoint.request_finished(12.5);
+        let text = registry.to_prometheus();
+        assert!(text.contains("dynamo_requests_total{endpoint=\"chat\"} 1"));
+    }
+
+    #[test]
+    fn same_endpoint_name_shares_one_metrics_instance() {
+        let registry = MetricsRegistry::new();
+        registry.endpoint("chat").request_started();
+        registry.endpoint("chat").request_started();
+        let text = registry.to_prometheus();
+        assert!(text.contains("dynamo_requests_total{endpoint=\"chat\"} 2"));
+    }
+}
@@ -0,0 +1,454 @@
+//! Priority-aware admission in front of engine dispatch: once the
+//! configured concurrency limit is reached, pending requests are
+//! released to the inner engine in priority order (ties broken by
+//! whichever has the least deadline budget left) instead of first in,
+//! first out, and a backend that implements [`Preemptible`] gets an
+//! advisory hint to give up lower-priority in-flight work when
+//! something more urgent is stuck waiting for a slot.
+
+use std::cmp::Ordering as CmpOrdering;
+use std::collections::BinaryHeap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+use tokio::sync::Notify;
+
+use crate::deadline::HasDeadline;
+use crate::engine::{AsyncEngine, ResponseStream};
+use crate::error::RuntimeError;
+
+/// What a request needs to expose so [`PriorityQueueEngine`] can order
+/// it against everything else waiting for a dispatch slot. Higher
+/// values are more urgent.
+pub trait HasPriority {
+    fn priority(&self) -> u8;
+}
+
+/// A backend that can act on a hint to give up lower-priority in-flight
+/// work to make room for something more urgent. Purely advisory: a
+/// backend that ignores the hint, or can't preempt anything right now,
+/// doesn't break anything — the high-priority request simply waits for
+/// the next slot to free up on its own.
+#[async_trait]
+pub trait Preemptible {
+    async fn preempt_below(&self, priority: u8);
+}
+
+struct Waiter {
+    id: u64,
+    priority: u8,
+    deadline_remaining: Duration,
+    notify: Arc<Notify>,
+}
+
+impl PartialEq for Waiter {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Waiter {}
+
+impl Ord for Waiter {
+    fn cmp(&self, other: &Self) -> CmpOrdering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.deadline_remaining.cmp(&self.deadline_remaining))
+            .then_with(|| other.id.cmp(&self.id))
+    }
+}
+
+impl PartialOrd for Waiter {
+    fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Default)]
+struct QueueState {
+    waiting: BinaryHeap<Waiter>,
+    in_flight: Vec<u8>,
+    next_id: u64,
+}
+
+fn dispatch_locked(max_concurrent: usize, state: &mut QueueState) {
+    while state.in_flight.len() < max_concurrent {
+        match state.waiting.pop() {
+            Some(waiter) => {
+                state.in_flight.push(waiter.priority);
+                waiter.notify.notify_one();
+            }
+            None => break,
+        }
+    }
+}
+
+struct Inner {
+    max_concurrent: usize,
+    state: Mutex<QueueState>,
+    preemptible: Option<Arc<dyn Preemptible + Send + Sync>>,
+}
+
+/// Shared priority-queue coordinator for one endpoint. Clone to hand
+/// the same queue to every [`PriorityQueueEngine`] wrapping that
+/// endpoint's dispatch path.
+#[derive(Clone)]
+pub struct PriorityQueueController {
+    inner: Arc<Inner>,
+}
+
+impl PriorityQueueController {
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                max_concurrent,
+                state: Mutex::new(QueueState::default()),
+                preemptible: None,
+            }),
+        }
+    }
+
+    /// Like [`PriorityQueueController::new`], but with a backend that
+    /// gets a [`Preemptible::preempt_below`] hint whenever a request
+    /// has to queue behind lower-priority in-flight work because every
+    /// slot is already in use.
+    pub fn with_preemption(
+        max_concurrent: usize,
+        backend: Arc<dyn Preemptible + Send + Sync>,
+    ) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                max_concurrent,
+                state: Mutex::new(QueueState::default()),
+                preemptible: Some(backend),
+            }),
+        }
+    }
+
+    pub fn queue_depth(&self) -> usize {
+        self.inner.state.lock().unwrap().waiting.len()
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.inner.state.lock().unwrap().in_flight.len()
+    }
+
+    /// Waits for a dispatch slot, admitting higher-priority waiters
+    /// ahead of `priority` as they arrive even if they were enqueued
+    /// later.
+    async fn admit(&self, priority: u8, deadline_remaining: Duration) -> PriorityTicket {
+        let notify = Arc::new(Notify::new());
+        let should_preempt;
+        {
+            let mut state = self.inner.state.lock().unwrap();
+            let id = state.next_id;
+            state.next_id += 1;
+            state.waiting.push(Waiter {
+                id,
+                priority,
+                deadline_remaining,
+                notify: notify.clone(),
+            });
+            dispatch_locked(self.inner.max_concurrent, &mut state);
+
+            should_preempt = state.waiting.iter().any(|w| w.id == id)
+                && state.in_flight.iter().any(|&p| p < priority);
+        }
+
+        if should_preempt {
+            if let Some(backend) = self.inner.preemptible.clone() {
+                tokio::spawn(async move { backend.preempt_below(priority).await });
+            }
+        }
+
+        notify.notified().await;
+        PriorityTicket {
+            inner: self.inner.clone(),
+            priority,
+        }
+    }
+}
+
+/// Releases the dispatch slot a request was admitted into, once the
+/// guard drops at the end of the request.
+struct PriorityTicket {
+    inner: Arc<Inner>,
+    priority: u8,
+}
+
+impl Drop for PriorityTicket {
+    fn drop(&mut self) {
+        let mut state = self.inner.state.lock().unwrap();
+        if let Some(pos) = state.in_flight.iter().position(|&p| p == self.priority) {
+            state.in_flight.remove(pos);
+        }
+        dispatch_locked(self.inner.max_concurrent, &mut state);
+    }
+}
+
+/// Wraps an `AsyncEngine` so requests queue for a dispatch slot in
+/// priority order instead of being forwarded to `inner` immediately.
+pub struct PriorityQueueEngine<E> {
+    inner: E,
+    controller: PriorityQueueController,
+}
+
+impl<E> PriorityQueueEngine<E> {
+    pub fn new(inner: E, controller: PriorityQueueController) -> Self {
+        Self { inner, controller }
+    }
+}
+
+#[async_trait]
+impl<E, Req, Resp> AsyncEngine<Req, Resp> for PriorityQueueEngine<E>
+where
+    E: AsyncEngine<Req, Resp>,
+    Req: HasPriority + HasDeadline + Send + 'static,
+    Resp: Send + 'static,
+{
+    async fn generate(&self, request: Req) -> Result<ResponseStream<Resp>, RuntimeError> {
+        let priority = request.priority();
+        let deadline_remaining = request
+            .deadline()
+            .map(|deadline| deadline.remaining())
+            .unwrap_or(Duration::MAX);
+        let ticket = self.controller.admit(priority, deadline_remaining).await;
+        let stream = self.inner.generate(request).await?;
+
+        // The ticket is held in the unfold state for the life of the
+        // stream, not just until `generate` returns, so the dispatch
+        // slot isn't freed (and `in_flight` doesn't drop) until the
+        // caller has actually finished reading the response — matching
+        // `drain::DrainableEngine` and `concurrency::ConcurrencyLimitedEngine`.
+        Ok(Box::pin(stream::unfold(
+            Some((stream, ticket)),
+            |cursor| async move {
+                let (mut stream, ticket) = cursor?;
+                stream
+                    .next()
+                    .await
+                    .map(|item| (item, Some((stream, ticket))))
+            },
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deadline::Deadline;
+    use futures::stream;
+    use std::sync::Mutex as StdMutex;
+
+    struct FakeRequest {
+        priority: u8,
+        deadline: Option<Deadline>,
+    }
+
+    impl HasPriority for FakeRequest {
+        fn priority(&self) -> u8 {
+            self.priority
+        }
+    }
+
+    impl HasDeadline for FakeRequest {
+        fn deadline(&self) -> Option<Deadline> {
+            self.deadline
+        }
+    }
+
+    struct FakeEngine;
+
+    #[async_trait]
+    impl AsyncEngine<FakeRequest, &'static str> for FakeEngine {
+        async fn generate(
+            &self,
+            _request: FakeRequest,
+        ) -> Result<ResponseStream<&'static str>, RuntimeError> {
+            Ok(Box::pin(stream::iter(vec![Ok("ok")])))
+        }
+    }
+
+    fn request(priority: u8) -> FakeRequest {
+        FakeRequest {
+            priority,
+            deadline: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn higher_priority_waiter_is_admitted_before_an_earlier_lower_priority_one() {
+        let controller = PriorityQueueController::new(1);
+        let held = controller.admit(1, Duration::MAX).await;
+
+        let order = Arc::new(StdMutex::new(Vec::new()));
+        let order_low = order.clone();
+        let controller_low = controller.clone();
+        let low = tokio::spawn(async move {
+            let _ticket = controller_low.admit(2, Duration::MAX).await;
+            order_low.lock().unwrap().push(2u8);
+        });
+
+        // Give the low-priority waiter time to enqueue before the
+        // higher-priority one arrives, so ordering is exercised rather
+        // than accidental arrival order.
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        let order_high = order.clone();
+        let controller_high = controller.clone();
+        let high = tokio::spawn(async move {
+            let _ticket = controller_high.admit(9, Duration::MAX).await;
+            order_high.lock().unwrap().push(9u8);
+        });
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        drop(held);
+        low.await.unwrap();
+        high.await.unwrap();
+
+        assert_eq!(*order.lock().unwrap(), vec![9, 2]);
+    }
+
+    #[tokio::test]
+    async fn ties_are_broken_by_tighter_deadline_first() {
+        let controller = PriorityQueueController::new(1);
+        let held = controller.admit(5, Duration::MAX).await;
+
+        let order = Arc::new(StdMutex::new(Vec::new()));
+        let controller_a = controller.clone();
+        let order_a = order.clone();
+        let loose = tokio::spawn(async move {
+            let _ticket = controller_a.admit(5, Duration::from_secs(10)).await;
+            order_a.lock().unwrap().push("loose");
+        });
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        let controller_b = controller.clone();
+        let order_b = order.clone();
+        let tight = tokio::spawn(async move {
+            let _ticket = controller_b.admit(5, Duration::from_millis(1)).await;
+            order_b.lock().unwrap().push("tight");
+        });
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        drop(held);
+        loose.await.unwrap();
+        tight.await.unwrap();
+
+        assert_eq!(*order.lock().unwrap(), vec!["tight", "loose"]);
+    }
+
+    struct RecordingPreemptible {
+        calls: StdMutex<Vec<u8>>,
+    }
+
+    #[async_trait]
+    impl Preemptible for RecordingPreemptible {
+        async fn preempt_below(&self, priority: u8) {
+            self.calls.lock().unwrap().push(priority);
+        }
+    }
+
+    #[tokio::test]
+    async fn stalled_high_priority_waiter_triggers_a_preemption_hint() {
+        let backend = Arc::new(RecordingPreemptible {
+            calls: StdMutex::new(Vec::new()),
+        });
+        let controller = PriorityQueueController::with_preemption(1, backend.clone());
+        let held = controller.admit(1, Duration::MAX).await;
+
+        // `admit` only returns once a slot frees up, which can't happen
+        // until `held` drops below, so spawn it and check for the
+        // advisory hint instead of waiting on its result.
+        let controller_high = controller.clone();
+        let high = tokio::spawn(async move { controller_high.admit(9, Duration::MAX).await });
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert_eq!(*backend.calls.lock().unwrap(), vec![9]);
+
+        drop(held);
+        high.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn engine_forwards_the_request_once_a_slot_is_admitted() {
+        let controller = PriorityQueueController::new(2);
+        let engine = PriorityQueueEngine::new(FakeEngine, controller);
+        assert!(engine.generate(request(5)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn queue_depth_and_in_flight_reflect_admitted_and_waiting_requests() {
+        let controller = PriorityQueueController::new(1);
+        let held = controller.admit(1, Duration::MAX).await;
+        assert_eq!(controller.in_flight(), 1);
+        assert_eq!(controller.queue_depth(), 0);
+
+        let controller_waiting = controller.clone();
+        let waiting = tokio::spawn(async move {
+            let _ticket = controller_waiting.admit(1, Duration::MAX).await;
+        });
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert_eq!(controller.queue_depth(), 1);
+
+        drop(held);
+        waiting.await.unwrap();
+    }
+
+    struct SlowEngine {
+        release: Arc<Notify>,
+    }
+
+    #[async_trait]
+    impl AsyncEngine<FakeRequest, &'static str> for SlowEngine {
+        async fn generate(
+            &self,
+            _request: FakeRequest,
+        ) -> Result<ResponseStream<&'static str>, RuntimeError> {
+            let release = self.release.clone();
+            Ok(Box::pin(stream::unfold(Some(()), move |state| {
+                let release = release.clone();
+                async move {
+                    state?;
+                    release.notified().await;
+                    Some(("chunk", None))
+                }
+            }))
+            .map(Ok)
+            .boxed())
+        }
+    }
+
+    #[tokio::test]
+    async fn in_flight_stays_elevated_and_a_waiter_stays_queued_while_the_stream_is_still_open() {
+        let release = Arc::new(Notify::new());
+        let controller = PriorityQueueController::new(1);
+        let engine = PriorityQueueEngine::new(
+            SlowEngine {
+                release: release.clone(),
+            },
+            controller.clone(),
+        );
+
+        let mut stream = engine.generate(request(1)).await.unwrap();
+
+        // The stream hasn't been polled to completion yet, so the
+        // dispatch slot it took must still be held.
+        assert_eq!(controller.in_flight(), 1);
+
+        let controller_waiting = controller.clone();
+        let waiting = tokio::spawn(async move {
+            let _ticket = controller_waiting.admit(1, Duration::MAX).await;
+        });
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert_eq!(controller.queue_depth(), 1);
+
+        release.notify_one();
+        stream.next().await.unwrap().unwrap();
+        drop(stream);
+
+        waiting.await.unwrap();
+        assert_eq!(controller.in_flight(), 0);
+    }
+}
@@ -0,0 +1,278 @@
+//! Per-endpoint tail-latency controls for egress clients: retries with
+//! backoff for idempotent endpoints, and request hedging (race a second
+//! worker once the first is slow, keep whichever answers first).
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+
+use crate::engine::{AsyncEngine, ResponseStream};
+use crate::error::RuntimeError;
+
+/// How an endpoint's client should retry a failed call.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total attempts including the first, when `idempotent` is set.
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Only requests at endpoints marked idempotent are retried; a
+    /// non-idempotent endpoint gets exactly one attempt so a retry can
+    /// never duplicate a side effect the first attempt already caused.
+    pub idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(50),
+            max_backoff: Duration::from_secs(2),
+            idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Backoff after failed attempt `attempt` (0-indexed), i.e. before
+    /// attempt `attempt + 1`, doubling each time and capped at
+    /// `max_backoff`.
+    fn backoff_for(&self, attempt: u32) -> Duration {
+        let scaled = self.initial_backoff.saturating_mul(1u32 << attempt.min(16));
+        scaled.min(self.max_backoff)
+    }
+}
+
+/// Wraps an `AsyncEngine` with [`RetryPolicy`]: retries a failed call
+/// with exponential backoff, up to `max_attempts` attempts in total, but
+/// only for endpoints explicitly marked idempotent.
+pub struct RetryingEngine<E> {
+    inner: E,
+    policy: RetryPolicy,
+}
+
+impl<E> RetryingEngine<E> {
+    pub fn new(inner: E, policy: RetryPolicy) -> Self {
+        Self { inner, policy }
+    }
+}
+
+#[async_trait]
+impl<E, Req, Resp> AsyncEngine<Req, Resp> for RetryingEngine<E>
+where
+    E: AsyncEngine<Req, Resp>,
+    Req: Clone + Send + 'static,
+    Resp: Send + 'static,
+{
+    async fn generate(&self, request: Req) -> Result<ResponseStream<Resp>, RuntimeError> {
+        let max_attempts = if self.policy.idempotent {
+            self.policy.max_attempts.max(1)
+        } else {
+            1
+        };
+
+        let mut attempt = 0;
+        loop {
+            match self.inner.generate(request.clone()).await {
+                Ok(stream) => return Ok(stream),
+                Err(_err) if attempt + 1 < max_attempts => {
+                    tokio::time::sleep(self.policy.backoff_for(attempt)).await;
+                    attempt += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+/// How long to wait for a primary worker's first response chunk before
+/// firing an identical request at a secondary worker.
+#[derive(Debug, Clone)]
+pub struct HedgingPolicy {
+    pub hedge_after: Duration,
+}
+
+/// Waits for the first item of `stream`, returning it alongside the
+/// remainder so the caller can splice it back onto the front once a
+/// winner is picked.
+async fn first_chunk<Resp: Send + 'static>(
+    mut stream: ResponseStream<Resp>,
+) -> (Option<Result<Resp, RuntimeError>>, ResponseStream<Resp>) {
+    let first = stream.next().await;
+    (first, stream)
+}
+
+/// Wraps two `AsyncEngine`s (typically two replicas of the same
+/// endpoint) with request hedging: calls `primary` first, and if it
+/// hasn't produced a first response chunk within `hedge_after`, fires
+/// the same request at `secondary` too. Whichever produces a chunk
+/// first wins; the other is dropped, cancelling it rather than letting
+/// it run to completion for nothing.
+pub struct HedgedEngine<E> {
+    primary: E,
+    secondary: E,
+    policy: HedgingPolicy,
+}
+
+impl<E> HedgedEngine<E> {
+    pub fn new(primary: E, secondary: E, policy: HedgingPolicy) -> Self {
+        Self {
+            primary,
+            secondary,
+            policy,
+        }
+    }
+}
+
+#[async_trait]
+impl<E, Req, Resp> AsyncEngine<Req, Resp> for HedgedEngine<E>
+where
+    E: AsyncEngine<Req, Resp>,
+    Req: Clone + Send + Sync + 'static,
+    Resp: Send + 'static,
+{
+    async fn generate(&self, request: Req) -> Result<ResponseStream<Resp>, RuntimeError> {
+        let secondary_request = request.clone();
+        let primary_fut = async {
+            let stream = self.primary.generate(request).await?;
+            Ok::<_, RuntimeError>(first_chunk(stream).await)
+        };
+        tokio::pin!(primary_fut);
+
+        let (first, rest) =
+            match tokio::time::timeout(self.policy.hedge_after, &mut primary_fut).await {
+                Ok(result) => result?,
+                Err(_elapsed) => {
+                    let secondary_fut = async {
+                        let stream = self.secondary.generate(secondary_request).await?;
+                        Ok::<_, RuntimeError>(first_chunk(stream).await)
+                    };
+                    tokio::select! {
+                        result = &mut primary_fut => result?,
+                        result = secondary_fut => result?,
+                    }
+                }
+            };
+
+        Ok(Box::pin(stream::iter(first).chain(rest)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    struct FailNTimesEngine {
+        failures_left: AtomicU32,
+    }
+
+    #[async_trait]
+    impl AsyncEngine<(), &'static str> for FailNTimesEngine {
+        async fn generate(
+            &self,
+            _request: (),
+        ) -> Result<ResponseStream<&'static str>, RuntimeError> {
+            if self
+                .failures_left
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| {
+                    if n > 0 {
+                        Some(n - 1)
+                    } else {
+                        None
+                    }
+                })
+                .is_ok()
+            {
+                Err(RuntimeError::Upstream("transient".to_string()))
+            } else {
+                Ok(Box::pin(stream::iter(vec![Ok("ok")])))
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn non_idempotent_endpoint_does_not_retry() {
+        let engine = RetryingEngine::new(
+            FailNTimesEngine {
+                failures_left: AtomicU32::new(1),
+            },
+            RetryPolicy {
+                idempotent: false,
+                ..RetryPolicy::default()
+            },
+        );
+        assert!(engine.generate(()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn idempotent_endpoint_retries_until_success() {
+        let engine = RetryingEngine::new(
+            FailNTimesEngine {
+                failures_left: AtomicU32::new(2),
+            },
+            RetryPolicy {
+                max_attempts: 3,
+                initial_backoff: Duration::from_millis(1),
+                max_backoff: Duration::from_millis(5),
+                idempotent: true,
+            },
+        );
+        assert!(engine.generate(()).await.is_ok());
+    }
+
+    struct SlowEngine {
+        delay: Duration,
+        label: &'static str,
+    }
+
+    #[async_trait]
+    impl AsyncEngine<(), &'static str> for SlowEngine {
+        async fn generate(
+            &self,
+            _request: (),
+        ) -> Result<ResponseStream<&'static str>, RuntimeError> {
+            tokio::time::sleep(self.delay).await;
+            Ok(Box::pin(stream::iter(vec![Ok(self.label)])))
+        }
+    }
+
+    #[tokio::test]
+    async fn hedges_to_secondary_when_primary_is_slow() {
+        let engine = HedgedEngine::new(
+            SlowEngine {
+                delay: Duration::from_millis(50),
+                label: "primary",
+            },
+            SlowEngine {
+                delay: Duration::from_millis(1),
+                label: "secondary",
+            },
+            HedgingPolicy {
+                hedge_after: Duration::from_millis(5),
+            },
+        );
+        let mut stream = engine.generate(()).await.unwrap();
+        assert_eq!(stream.next().await.unwrap().unwrap(), "secondary");
+    }
+
+    #[tokio::test]
+    async fn fast_primary_never_triggers_hedge() {
+        let engine = HedgedEngine::new(
+            SlowEngine {
+                delay: Duration::from_millis(1),
+                label: "primary",
+            },
+            SlowEngine {
+                delay: Duration::from_millis(50),
+                label: "secondary",
+            },
+            HedgingPolicy {
+                hedge_after: Duration::from_millis(20),
+            },
+        );
+        let mut stream = engine.generate(()).await.unwrap();
+        assert_eq!(stream.next().await.unwrap().unwrap(), "primary");
+    }
+}
@@ -0,0 +1,171 @@
+//! Distributed tracing: a W3C `traceparent`-compatible context that
+//! can be injected into and extracted from message-bus headers, so a
+//! request crossing frontend → router → worker shows up as one trace
+//! instead of three disconnected ones, plus a span exporter seam for
+//! shipping finished spans to an OTLP collector.
+
+use std::collections::HashMap;
+
+const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// A W3C trace context: which trace a span belongs to, which span it
+/// is, and whether the trace is sampled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceContext {
+    pub trace_id: [u8; 16],
+    pub span_id: [u8; 8],
+    pub sampled: bool,
+}
+
+impl TraceContext {
+    /// Starts a new trace with a fresh trace id and root span id.
+    pub fn new_root(trace_id: [u8; 16], span_id: [u8; 8], sampled: bool) -> Self {
+        Self {
+            trace_id,
+            span_id,
+            sampled,
+        }
+    }
+
+    /// Derives a child span's context: same trace, a new span id.
+    pub fn child(&self, span_id: [u8; 8]) -> Self {
+        Self {
+            trace_id: self.trace_id,
+            span_id,
+            sampled: self.sampled,
+        }
+    }
+
+    /// Renders as a W3C `traceparent` value: `version-trace_id-span_id-flags`.
+    pub fn to_traceparent(&self) -> String {
+        format!(
+            "00-{}-{}-{:02x}",
+            hex(&self.trace_id),
+            hex(&self.span_id),
+            if self.sampled { 1u8 } else { 0u8 }
+        )
+    }
+
+    /// Parses a W3C `traceparent` value. Returns `None` on any
+    /// malformed field rather than partially trusting it.
+    pub fn from_traceparent(value: &str) -> Option<Self> {
+        let parts: Vec<&str> = value.split('-').collect();
+        if parts.len() != 4 || parts[0] != "00" {
+            return None;
+        }
+        let trace_id = unhex::<16>(parts[1])?;
+        let span_id = unhex::<8>(parts[2])?;
+        let flags = u8::from_str_radix(parts[3], 16).ok()?;
+        Some(Self {
+            trace_id,
+            span_id,
+            sampled: flags & 1 == 1,
+        })
+    }
+
+    /// Injects this context into message-bus headers ahead of the next
+    /// hop.
+    pub fn inject(&self, headers: &mut HashMap<String, String>) {
+        headers.insert(TRACEPARENT_HEADER.to_string(), self.to_traceparent());
+    }
+
+    /// Extracts a trace context carried across the previous hop, if
+    /// any.
+    pub fn extract(headers: &HashMap<String, String>) -> Option<Self> {
+        headers
+            .get(TRACEPARENT_HEADER)
+            .and_then(|v| Self::from_traceparent(v))
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn unhex<const N: usize>(s: &str) -> Option<[u8; N]> {
+    if s.len() != N * 2 {
+        return None;
+    }
+    let mut out = [0u8; N];
+    for i in 0..N {
+        out[i] = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(out)
+}
+
+/// One finished span, ready to export.
+#[derive(Debug, Clone)]
+pub struct FinishedSpan {
+    pub name: String,
+    pub context: TraceContext,
+    pub parent_span_id: Option<[u8; 8]>,
+    pub start_s: f64,
+    pub end_s: f64,
+    pub attributes: HashMap<String, String>,
+}
+
+/// Where finished spans go. A real implementation ships them to an
+/// OTLP collector; that dependency isn't wired into this crate yet, so
+/// [`RecordingExporter`] stands in for tests and local inspection in
+/// the meantime, the same pattern used for the mocker's
+/// `SignalPublisher`.
+pub trait SpanExporter: Send + Sync {
+    fn export(&self, span: FinishedSpan);
+}
+
+/// No-op exporter for configurations that don't want tracing overhead.
+pub struct NoopExporter;
+
+impl SpanExporter for NoopExporter {
+    fn export(&self, _span: FinishedSpan) {}
+}
+
+/// Collects exported spans in memory, for tests and for a local
+/// `/spans` debug endpoint until a real OTLP exporter exists.
+#[derive(Default)]
+pub struct RecordingExporter {
+    spans: std::sync::Mutex<Vec<FinishedSpan>>,
+}
+
+impl RecordingExporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn spans(&self) -> Vec<FinishedSpan> {
+        self.spans.lock().unwrap().clone()
+    }
+}
+
+impl SpanExporter for RecordingExporter {
+    fn export(&self, span: FinishedSpan) {
+        self.spans.lock().unwrap().push(span);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn traceparent_round_trips() {
+        let ctx = TraceContext::new_root([1; 16], [2; 8], true);
+        let rendered = ctx.to_traceparent();
+        let parsed = TraceContext::from_traceparent(&rendered).unwrap();
+        assert_eq!(ctx, parsed);
+    }
+
+    #[test]
+    fn extract_finds_injected_context_in_headers() {
+        let ctx = TraceContext::new_root([9; 16], [8; 8], false);
+        let mut headers = HashMap::new();
+        ctx.inject(&mut headers);
+        let extracted = TraceContext::extract(&headers).unwrap();
+        assert_eq!(extracted, ctx);
+    }
+
+    #[test]
+    fn malformed_traceparent_is_rejected() {
+        assert!(TraceContext::from_traceparent("not-a-traceparent").is_none());
+    }
+}
@@ -0,0 +1,263 @@
+//! The message plane: how requests get distributed to workers and how
+//! KV/event-plane traffic gets published and subscribed to, abstracted
+//! behind a trait so NATS isn't the only option — several target
+//! environments already standardize on Redis Streams or Kafka and
+//! can't deploy a NATS cluster alongside them.
+
+use async_trait::async_trait;
+
+use crate::error::RuntimeError;
+
+/// One message on the plane: an opaque payload on a named subject or
+/// topic, however the backend names that concept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    pub subject: String,
+    pub payload: Vec<u8>,
+}
+
+/// Publishes to and polls from the event/request plane. Request
+/// distribution and KV/event publication are both built on this one
+/// trait; only the backend underneath changes.
+#[async_trait]
+pub trait MessagePlane: Send + Sync {
+    async fn publish(&self, subject: &str, payload: Vec<u8>) -> Result<(), RuntimeError>;
+
+    /// Pulls up to `max` messages published to `subject` since the last
+    /// call. Backed by a subscription, a consumer group, or a
+    /// partition read depending on the implementation; callers don't
+    /// need to know which.
+    async fn poll(&self, subject: &str, max: usize) -> Result<Vec<Message>, RuntimeError>;
+}
+
+/// The NATS operations the message plane needs: publish, and pull the
+/// next batch for a subject from a queue-group subscription (so
+/// multiple workers polling the same subject split the load instead of
+/// each getting every message). No `async-nats` dependency lives in
+/// this crate, so [`NatsMessagePlane`] is written against this seam.
+#[async_trait]
+pub trait NatsClient: Send + Sync {
+    async fn publish(&self, subject: &str, payload: Vec<u8>) -> Result<(), RuntimeError>;
+    async fn next_batch(&self, subject: &str, max: usize) -> Result<Vec<Vec<u8>>, RuntimeError>;
+}
+
+pub struct NatsMessagePlane<C> {
+    client: C,
+}
+
+impl<C: NatsClient> NatsMessagePlane<C> {
+    pub fn new(client: C) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl<C: NatsClient> MessagePlane for NatsMessagePlane<C> {
+    async fn publish(&self, subject: &str, payload: Vec<u8>) -> Result<(), RuntimeError> {
+        self.client.publish(subject, payload).await
+    }
+
+    async fn poll(&self, subject: &str, max: usize) -> Result<Vec<Message>, RuntimeError> {
+        Ok(self
+            .client
+            .next_batch(subject, max)
+            .await?
+            .into_iter()
+            .map(|payload| Message {
+                subject: subject.to_string(),
+                payload,
+            })
+            .collect())
+    }
+}
+
+/// The Redis Streams operations the message plane needs: `XADD` to
+/// publish, and `XREADGROUP`-style reads against a consumer group so
+/// concurrent pollers split a stream's entries. No `redis` dependency
+/// lives in this crate, so [`RedisStreamsMessagePlane`] is written
+/// against this seam.
+#[async_trait]
+pub trait RedisStreamsClient: Send + Sync {
+    async fn xadd(&self, stream: &str, payload: Vec<u8>) -> Result<(), RuntimeError>;
+    async fn xreadgroup(&self, stream: &str, count: usize) -> Result<Vec<Vec<u8>>, RuntimeError>;
+}
+
+pub struct RedisStreamsMessagePlane<C> {
+    client: C,
+}
+
+impl<C: RedisStreamsClient> RedisStreamsMessagePlane<C> {
+    pub fn new(client: C) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl<C: RedisStreamsClient> MessagePlane for RedisStreamsMessagePlane<C> {
+    async fn publish(&self, subject: &str, payload: Vec<u8>) -> Result<(), RuntimeError> {
+        self.client.xadd(subject, payload).await
+    }
+
+    async fn poll(&self, subject: &str, max: usize) -> Result<Vec<Message>, RuntimeError> {
+        Ok(self
+            .client
+            .xreadgroup(subject, max)
+            .await?
+            .into_iter()
+            .map(|payload| Message {
+                subject: subject.to_string(),
+                payload,
+            })
+            .collect())
+    }
+}
+
+/// The Kafka operations the message plane needs: produce to a topic,
+/// and poll records for a consumer group off a topic. No
+/// `rdkafka`/`kafka` dependency lives in this crate, so
+/// [`KafkaMessagePlane`] is written against this seam.
+#[async_trait]
+pub trait KafkaClient: Send + Sync {
+    async fn produce(&self, topic: &str, payload: Vec<u8>) -> Result<(), RuntimeError>;
+    async fn poll_records(&self, topic: &str, max: usize) -> Result<Vec<Vec<u8>>, RuntimeError>;
+}
+
+pub struct KafkaMessagePlane<C> {
+    client: C,
+}
+
+impl<C: KafkaClient> KafkaMessagePlane<C> {
+    pub fn new(client: C) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl<C: KafkaClient> MessagePlane for KafkaMessagePlane<C> {
+    async fn publish(&self, subject: &str, payload: Vec<u8>) -> Result<(), RuntimeError> {
+        self.client.produce(subject, payload).await
+    }
+
+    async fn poll(&self, subject: &str, max: usize) -> Result<Vec<Message>, RuntimeError> {
+        Ok(self
+            .client
+            .poll_records(subject, max)
+            .await?
+            .into_iter()
+            .map(|payload| Message {
+                subject: subject.to_string(),
+                payload,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct FakeBroker {
+        queues: Mutex<HashMap<String, Vec<Vec<u8>>>>,
+    }
+
+    impl FakeBroker {
+        fn push(&self, subject: &str, payload: Vec<u8>) {
+            self.queues
+                .lock()
+                .unwrap()
+                .entry(subject.to_string())
+                .or_default()
+                .push(payload);
+        }
+
+        fn drain(&self, subject: &str, max: usize) -> Vec<Vec<u8>> {
+            let mut queues = self.queues.lock().unwrap();
+            let queue = queues.entry(subject.to_string()).or_default();
+            let drained: Vec<_> = queue.drain(..queue.len().min(max)).collect();
+            drained
+        }
+    }
+
+    #[async_trait]
+    impl NatsClient for FakeBroker {
+        async fn publish(&self, subject: &str, payload: Vec<u8>) -> Result<(), RuntimeError> {
+            self.push(subject, payload);
+            Ok(())
+        }
+
+        async fn next_batch(
+            &self,
+            subject: &str,
+            max: usize,
+        ) -> Result<Vec<Vec<u8>>, RuntimeError> {
+            Ok(self.drain(subject, max))
+        }
+    }
+
+    #[async_trait]
+    impl RedisStreamsClient for FakeBroker {
+        async fn xadd(&self, stream: &str, payload: Vec<u8>) -> Result<(), RuntimeError> {
+            self.push(stream, payload);
+            Ok(())
+        }
+
+        async fn xreadgroup(
+            &self,
+            stream: &str,
+            count: usize,
+        ) -> Result<Vec<Vec<u8>>, RuntimeError> {
+            Ok(self.drain(stream, count))
+        }
+    }
+
+    #[async_trait]
+    impl KafkaClient for FakeBroker {
+        async fn produce(&self, topic: &str, payload: Vec<u8>) -> Result<(), RuntimeError> {
+            self.push(topic, payload);
+            Ok(())
+        }
+
+        async fn poll_records(
+            &self,
+            topic: &str,
+            max: usize,
+        ) -> Result<Vec<Vec<u8>>, RuntimeError> {
+            Ok(self.drain(topic, max))
+        }
+    }
+
+    #[tokio::test]
+    async fn nats_plane_round_trips_a_message() {
+        let plane = NatsMessagePlane::new(FakeBroker::default());
+        plane.publish("kv.events", b"hello".to_vec()).await.unwrap();
+        let messages = plane.poll("kv.events", 10).await.unwrap();
+        assert_eq!(
+            messages,
+            vec![Message {
+                subject: "kv.events".to_string(),
+                payload: b"hello".to_vec(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn redis_streams_plane_round_trips_a_message() {
+        let plane = RedisStreamsMessagePlane::new(FakeBroker::default());
+        plane.publish("kv.events", b"hello".to_vec()).await.unwrap();
+        let messages = plane.poll("kv.events", 10).await.unwrap();
+        assert_eq!(messages[0].payload, b"hello");
+    }
+
+    #[tokio::test]
+    async fn kafka_plane_respects_max_batch_size() {
+        let plane = KafkaMessagePlane::new(FakeBroker::default());
+        for i in 0..5u8 {
+            plane.publish("kv.events", vec![i]).await.unwrap();
+        }
+        let messages = plane.poll("kv.events", 3).await.unwrap();
+        assert_eq!(messages.len(), 3);
+    }
+}
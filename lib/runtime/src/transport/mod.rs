@@ -0,0 +1,5 @@
+//! Alternative transports for inter-component `AsyncEngine` calls,
+//! beyond the default NATS/TCP message-bus path.
+
+pub mod grpc;
+pub mod message_plane;